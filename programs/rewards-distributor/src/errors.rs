@@ -49,4 +49,264 @@ pub enum ErrorCode {
     ///Error indicating that the operation cannot be performed due to an invalid mint account.
     #[msg("Operation not allowed: Invalid mint account.")]
     InvalidMintAccount,
+
+    /// Error indicating that the claim authorization is past its expiry.
+    #[msg("Operation not allowed: Claim authorization has expired.")]
+    AuthorizationExpired,
+
+    /// Error indicating that no valid receiver signature was found in the transaction.
+    #[msg("Missing or invalid ed25519 claim authorization.")]
+    InvalidAuthorization,
+
+    /// Error indicating that the remaining accounts do not match the claim entries.
+    #[msg("Remaining accounts do not match the claim entries.")]
+    InvalidRemainingAccounts,
+
+    /// Error indicating that the instruction is not available in the current distribution mode.
+    #[msg("Operation not allowed: Invalid distribution mode.")]
+    InvalidDistributionMode,
+
+    /// Error indicating that the distribution mode cannot change once epochs exist.
+    #[msg("Operation not allowed: Distribution mode is locked once epochs exist.")]
+    DistributionModeLocked,
+
+    /// Error indicating that everything owed to the receiver was already claimed.
+    #[msg("Nothing left to claim")]
+    NothingToClaim,
+
+    /// Error indicating that the previous epoch accounts are required but missing or invalid.
+    #[msg("Operation not allowed: Invalid previous epoch accounts.")]
+    InvalidPreviousEpoch,
+
+    /// Error indicating that the claim tracking accounts do not match the epoch.
+    #[msg("Claim tracking account does not match the epoch.")]
+    InvalidClaimTracking,
+
+    /// Error indicating that a bitmap shard is allocated out of order.
+    #[msg("Operation not allowed: Invalid claim bitmap shard.")]
+    InvalidClaimBitmapShard,
+
+    /// Error indicating that claims were already tracked with claim status accounts.
+    #[msg("Operation not allowed: Epoch already has claims tracked by claim status.")]
+    ClaimTrackingLocked,
+
+    /// Error indicating that the unclaimed rewards of the epoch were swept.
+    #[msg("Operation not allowed: Epoch rewards were swept.")]
+    EpochSwept,
+
+    /// Error indicating that the claim deadline of the epoch has passed.
+    #[msg("Operation not allowed: Claim deadline has passed.")]
+    ClaimDeadlinePassed,
+
+    /// Error indicating that the claim deadline of the epoch has not passed yet.
+    #[msg("Operation not allowed: Claim deadline has not passed yet.")]
+    ClaimDeadlineNotReached,
+
+    /// Error indicating that the claim window is invalid.
+    #[msg("Operation not allowed: Invalid claim window.")]
+    InvalidClaimWindow,
+
+    /// Error indicating that the token account is not owned by the treasury.
+    #[msg("Token account is not owned by the treasury.")]
+    InvalidTreasury,
+
+    /// Error indicating that the epoch can still be claimed, so its claims cannot be closed.
+    #[msg("Operation not allowed: Epoch can still be claimed.")]
+    EpochStillClaimable,
+
+    /// Error indicating that the epoch vault still holds tokens.
+    #[msg("Operation not allowed: Epoch vault is not empty.")]
+    EpochVaultNotEmpty,
+
+    /// Error indicating that the rent is not refunded to the account that paid it.
+    #[msg("Rent payer mismatch.")]
+    InvalidRentPayer,
+
+    /// Error indicating that the mint cannot be transferred.
+    #[msg("Unsupported mint: Non-transferable tokens cannot be distributed.")]
+    NonTransferableMint,
+
+    /// Error indicating that the mint has a permanent delegate that could drain the vaults.
+    #[msg("Unsupported mint: Permanent delegate.")]
+    PermanentDelegateMint,
+
+    /// Error indicating that the mint transfers need a transfer hook program.
+    #[msg("Unsupported mint: Transfer hook.")]
+    TransferHookMint,
+
+    /// Error indicating that the mint supports confidential transfers.
+    #[msg("Unsupported mint: Confidential transfers.")]
+    ConfidentialTransferMint,
+
+    /// Error indicating that the transfer fee of the mint cannot be computed.
+    #[msg("Invalid transfer fee.")]
+    InvalidTransferFee,
+
+    /// Error indicating that the epoch does not distribute native SOL.
+    #[msg("Operation not allowed: Epoch does not distribute native SOL.")]
+    NotNativeEpoch,
+
+    /// Error indicating that the vault cannot pay the claim and stay rent-exempt.
+    #[msg("Insufficient vault balance.")]
+    InsufficientVaultBalance,
+
+    /// Error indicating that the epoch has no mint at the given index.
+    #[msg("Invalid mint index.")]
+    InvalidMintIndex,
+
+    /// Error indicating that the epoch already distributes the mint.
+    #[msg("Operation not allowed: Epoch already distributes this mint.")]
+    DuplicateEpochMint,
+
+    /// Error indicating that the epoch cannot distribute more mints.
+    #[msg("Operation not allowed: Too many mints for the epoch.")]
+    TooManyEpochMints,
+
+    /// Error indicating that the declared totals of the epoch are zero.
+    #[msg("Invalid epoch totals.")]
+    InvalidEpochTotals,
+
+    /// Error indicating that the approved amount differs from the declared total of the epoch.
+    #[msg("Approved amount does not match the declared total of the epoch.")]
+    EpochTotalMismatch,
+
+    /// Error indicating that the balance carried or rolled over exceeds the declared total of the epoch.
+    #[msg("Carried over balance exceeds the declared total of the epoch.")]
+    EpochOverfunded,
+
+    /// Error indicating that the claim would exceed the declared total of the epoch.
+    #[msg("Claim exceeds the declared total of the epoch.")]
+    MaxTotalClaimExceeded,
+
+    /// Error indicating that the claim would exceed the declared number of nodes of the epoch.
+    #[msg("Claim exceeds the declared number of nodes of the epoch.")]
+    MaxNumNodesExceeded,
+
+    /// Error indicating that the epoch was corrected since the manager reviewed it.
+    #[msg("Epoch changed since it was reviewed.")]
+    EpochChangedSinceReview,
+
+    /// Error indicating that the epoch was rejected and not resubmitted yet.
+    #[msg("Operation not allowed: Epoch was rejected and must be resubmitted.")]
+    EpochRejected,
+
+    /// Error indicating that the challenge window is negative.
+    #[msg("Invalid challenge window.")]
+    InvalidChallengeWindow,
+
+    /// Error indicating that the claims of the epoch are not open yet.
+    #[msg("Operation not allowed: Claims are not open yet.")]
+    ClaimsNotOpen,
+
+    /// Error indicating that the epoch is not in its challenge window.
+    #[msg("Operation not allowed: Epoch is not in its challenge window.")]
+    ChallengeWindowClosed,
+
+    /// Error indicating that the metadata URI of the epoch is too long.
+    #[msg("Metadata URI is too long.")]
+    MetadataUriTooLong,
+
+    /// Error indicating that neither the treasury vault nor the manager token account was passed.
+    #[msg("Missing treasury vault or manager token account.")]
+    MissingFundingSource,
+
+    /// Error indicating that the program PDA is not the mint authority of the mint.
+    #[msg("Invalid mint authority.")]
+    InvalidMintAuthority,
+
+    /// Error indicating that a claim would mint past the emission cap.
+    #[msg("Claim exceeds the emission cap.")]
+    EmissionCapExceeded,
+
+    /// Error indicating that the instruction cannot pay leaves minted by the program.
+    #[msg("Operation not allowed: Epoch is in mint mode.")]
+    MintModeEpoch,
+
+    /// Error indicating that the emission schedule is invalid.
+    #[msg("Invalid emission schedule.")]
+    InvalidEmissionSchedule,
+
+    /// Error indicating that the epoch issues more than the emission schedule allows.
+    #[msg("Epoch exceeds the allowance of the emission schedule.")]
+    EmissionScheduleExceeded,
+
+    /// Error indicating that the members or threshold of the manager multisig are invalid.
+    #[msg("Invalid multisig.")]
+    InvalidMultisig,
+
+    /// Error indicating that the action must go through a proposal of the manager multisig.
+    #[msg("Operation not allowed: Action requires a multisig proposal.")]
+    ProposalRequired,
+
+    /// Error indicating that the proposal does not propose this action.
+    #[msg("Proposal does not match the action.")]
+    ProposalMismatch,
+
+    /// Error indicating that the proposal was already executed.
+    #[msg("Proposal already executed.")]
+    ProposalAlreadyExecuted,
+
+    /// Error indicating that the proposal does not have enough approvals.
+    #[msg("Proposal has not reached the multisig threshold.")]
+    ProposalThresholdNotReached,
+
+    /// Error indicating that the quorum agents or the agent quorum are invalid.
+    #[msg("Invalid agent quorum.")]
+    InvalidAgentQuorum,
+
+    /// Error indicating that not enough quorum agents submitted the root of the epoch.
+    #[msg("Epoch has not reached the agent quorum.")]
+    AgentQuorumNotReached,
+
+    /// Error indicating that the change must be queued as a timelock operation.
+    #[msg("Operation not allowed: Change requires a timelock operation.")]
+    TimelockRequired,
+
+    /// Error indicating that the timelock delay is negative.
+    #[msg("Invalid timelock delay.")]
+    InvalidTimelockDelay,
+
+    /// Error indicating that the timelock operation cannot be executed before its eta.
+    #[msg("Timelock operation not ready.")]
+    TimelockNotReady,
+
+    /// Error indicating that the timelock operation was already executed or cancelled.
+    #[msg("Timelock operation not pending.")]
+    TimelockOperationNotPending,
+
+    /// Error indicating that the overlap period of the agent is negative.
+    #[msg("Invalid agent overlap.")]
+    InvalidAgentOverlap,
+
+    /// Error indicating that there is no proposed agent or manager to cancel.
+    #[msg("No proposal to cancel.")]
+    NoPendingProposal,
+
+    /// Error indicating that the pause flags are empty or contain unknown bits.
+    #[msg("Invalid pause flags.")]
+    InvalidPauseFlags,
+
+    /// Error indicating that the pause expiry is in the past.
+    #[msg("Invalid pause expiry.")]
+    InvalidPauseExpiry,
+
+    /// Error indicating that the claim would exceed the amount funded for the mint.
+    #[msg("Claim exceeds the funded amount of the mint.")]
+    FundedAmountExceeded,
+
+    /// Error indicating that a top-up exceeds what the epoch vault lacks to pay its total.
+    #[msg("Top-up exceeds the shortfall of the epoch.")]
+    EpochShortfallExceeded,
+
+    /// Error indicating that the epoch cannot record top-ups from more funders.
+    #[msg("Too many funders topped up the epoch.")]
+    TooManyEpochTopUps,
+
+    /// Error indicating that an entry of a batched claim failed, with its position and error.
+    #[msg("Claim entry failed.")]
+    ClaimEntryFailed,
+
+    /// Error indicating that the epoch still has rewards to claim, neither claimed in full nor swept.
+    #[msg("Operation not allowed: Epoch rewards are not claimed or swept.")]
+    EpochNotSettled,
 }
@@ -1,3 +1,4 @@
+use crate::state::{DistributionMode, EmissionSchedule, ManagerAction, Role, TimelockAction};
 use anchor_lang::prelude::*;
 
 /// Event emitted when the program is initialized.
@@ -48,6 +49,14 @@ pub struct EpochCreated {
     pub agent: Pubkey,
     /// Mint
     pub mint: Pubkey,
+    /// Total amount, in base units, the Merkle tree pays.
+    pub max_total_claim: u64,
+    /// Number of leaves of the Merkle tree.
+    pub max_num_nodes: u64,
+    /// Location of the distribution file.
+    pub metadata_uri: String,
+    /// Hash of the distribution file.
+    pub distribution_hash: [u8; 32],
 }
 
 /// Event emitted when rewards are claimed.
@@ -60,10 +69,14 @@ pub struct ClaimedEvent {
     pub index: u64,
     /// Public key of the receiver claiming the reward.
     pub receiver: Pubkey,
-    /// Amount of the reward claimed.
+    /// Amount of the reward claimed, sent by the epoch.
     pub amount: u64,
+    /// Amount received once transfer fees are withheld.
+    pub net_amount: u64,
     /// Epoch number
     pub epoch_nr: u64,
+    /// Relayer who paid for a gasless claim, `None` if the receiver claimed directly.
+    pub relayer: Option<Pubkey>,
 }
 
 /// Event emitted when the agent is changed.
@@ -74,28 +87,414 @@ pub struct AgentChanged {
     pub new_agent: Pubkey,
 }
 
-/// Event emitted when the program is paused.
+/// Event emitted when a Proposed agent is set.
 ///
-/// This event contains a boolean that represents the pause state of the program.
+/// This event contains the public key of the Proposed agent and the overlap period, in
+/// seconds, of the current agent once it accepts.
+#[event]
+pub struct NewProposedAgent {
+    pub proposed_agent: Pubkey,
+    pub overlap: i64,
+}
+
+/// Event emitted when the manager cancels the proposal of a new agent.
+///
+/// This event contains the public key of the cancelled Proposed agent.
+#[event]
+pub struct ProposedAgentCancelled {
+    pub proposed_agent: Pubkey,
+}
+
+/// Event emitted when the manager cancels the proposal of a new manager.
+///
+/// This event contains the public key of the cancelled Proposed manager.
+#[event]
+pub struct ProposedManagerCancelled {
+    pub proposed_manager: Pubkey,
+}
+
+/// Event emitted when the program is paused or unpaused.
+///
+/// This event contains whether any operation is paused, the paused operations, the reason
+/// code of the pause and its expiry, 0 for none.
 #[event]
 pub struct Paused {
     pub is_paused: bool,
+    pub pause_flags: u8,
+    pub reason: u16,
+    pub expires_at: i64,
 }
 
 /// Event emitted when an epoch is corrected.
 ///
-/// This event contains the corrected root hash and the epoch number of the corrected epoch.
+/// This event contains the corrected root hash, the epoch number of the corrected epoch,
+/// its declared totals and the number of corrections made so far.
 #[event]
 pub struct EpochCorrected {
     pub root: [u8; 32],
     pub epoch_nr: u64,
     pub mint: Pubkey,
+    pub max_total_claim: u64,
+    pub max_num_nodes: u64,
+    pub correction_count: u64,
+    pub metadata_uri: String,
+    pub distribution_hash: [u8; 32],
+}
+
+/// Event emitted when the manager rejects an epoch.
+///
+/// This event contains the epoch number and the root hash of the rejected epoch,
+/// the reason code of the rejection and the number of rejections so far.
+#[event]
+pub struct EpochRejected {
+    pub epoch_nr: u64,
+    pub root: [u8; 32],
+    pub reason: u16,
+    pub rejection_count: u64,
 }
 
 /// Event emitted when an epoch is aproved.
 ///
-/// This event contains the epoch number of the approved epoch.
+/// This event contains the epoch number and the root hash of the approved epoch, the amount sent
+/// by the Manager and the amount received by the epoch once transfer fees are withheld.
 #[event]
 pub struct EpochApproved {
     pub epoch_nr: u64,
+    pub root: [u8; 32],
+    pub amount: u64,
+    pub net_amount: u64,
+}
+
+/// Event emitted when the distribution mode is changed.
+///
+/// This event contains the new distribution mode.
+#[event]
+pub struct DistributionModeChanged {
+    pub distribution_mode: DistributionMode,
+}
+
+/// Event emitted when a claim bitmap shard is allocated for an epoch.
+///
+/// This event contains the epoch number and the index of the shard.
+#[event]
+pub struct ClaimBitmapCreated {
+    pub epoch_nr: u64,
+    pub shard: u64,
+}
+
+/// Event emitted when the claim window is changed.
+///
+/// This event contains the new claim window in seconds.
+#[event]
+pub struct ClaimWindowChanged {
+    pub claim_window: i64,
+}
+
+/// Event emitted when the challenge window is changed.
+///
+/// This event contains the new challenge window in seconds.
+#[event]
+pub struct ChallengeWindowChanged {
+    pub challenge_window: i64,
+}
+
+/// Event emitted when the guardian is changed.
+///
+/// This event contains the pubkey of the new guardian.
+#[event]
+pub struct GuardianChanged {
+    pub guardian: Pubkey,
+}
+
+/// Event emitted when the funder is changed.
+///
+/// This event contains the pubkey of the new funder.
+#[event]
+pub struct FunderChanged {
+    pub funder: Pubkey,
+}
+
+/// Event emitted when an approved epoch is vetoed during its challenge window.
+///
+/// This event contains the epoch number and the root hash of the vetoed epoch,
+/// who vetoed it, the amount returned to the Manager and the amount returned to
+/// the funders who topped it up.
+#[event]
+pub struct EpochVetoed {
+    pub epoch_nr: u64,
+    pub root: [u8; 32],
+    pub vetoed_by: Pubkey,
+    pub amount: u64,
+    pub refunded_top_ups: u64,
+}
+
+/// Event emitted when the treasury is changed.
+///
+/// This event contains the pubkey of the new treasury.
+#[event]
+pub struct TreasuryChanged {
+    pub treasury: Pubkey,
+}
+
+/// Event emitted when the unclaimed rewards of an epoch are swept.
+///
+/// This event contains the epoch number, the mint, the amount recovered, the amount
+/// received once transfer fees are withheld and where it was sent. For native SOL epochs
+/// the mint is the default pubkey and the lamports are sent to the treasury itself.
+#[event]
+pub struct EpochSwept {
+    pub epoch_nr: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub net_amount: u64,
+    pub treasury_token_account: Pubkey,
+}
+
+/// Event emitted when the unclaimed rewards of an epoch are rolled over into a later epoch.
+///
+/// This event contains both epochs, the mint, the amount moved, the amount the next epoch
+/// received once transfer fees are withheld and the total the next epoch received for the
+/// mint so far: its `rolled_over_amount` for the primary mint of an unapproved epoch, its
+/// `funded_amount` otherwise.
+#[event]
+pub struct RewardsRolledOver {
+    pub epoch_nr: u64,
+    pub next_epoch_nr: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub net_amount: u64,
+    pub funded_amount: u64,
+}
+
+/// Event emitted when a claim status account is closed.
+///
+/// This event contains the epoch account, the index of the claim and who got the rent back.
+#[event]
+pub struct ClaimStatusClosed {
+    pub epoch_account: Pubkey,
+    pub index: u64,
+    pub payer: Pubkey,
+}
+
+/// Event emitted when an epoch account and its vault are closed.
+///
+/// This event contains the epoch number.
+#[event]
+pub struct EpochClosed {
+    pub epoch_nr: u64,
+}
+
+/// Event emitted when a mint is added to an epoch on top of its primary mint.
+///
+/// This event contains the epoch number, the mint, its index in the leaves and the new
+/// correction count of the epoch.
+#[event]
+pub struct EpochMintAdded {
+    pub epoch_nr: u64,
+    pub mint: Pubkey,
+    pub mint_index: u8,
+    pub correction_count: u64,
+}
+
+/// Event emitted when the ATA of an additional mint of an epoch is funded.
+///
+/// This event contains the epoch number, the mint, the amount sent by the Manager
+/// and the amount received once transfer fees are withheld.
+#[event]
+pub struct EpochMintFunded {
+    pub epoch_nr: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub net_amount: u64,
+}
+
+/// Event emitted when an approved epoch is topped up.
+///
+/// This event contains the epoch number, who funded it, the amount sent and the
+/// amount received once transfer fees are withheld, and the total funded so far.
+#[event]
+pub struct EpochFunded {
+    pub epoch_nr: u64,
+    pub funder: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub net_amount: u64,
+    pub funded_amount: u64,
+}
+
+/// Event emitted when tokens are deposited in a treasury vault.
+///
+/// This event contains the mint, the depositor, the amount sent and the amount
+/// received once transfer fees are withheld.
+#[event]
+pub struct TreasuryDeposited {
+    pub mint: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub net_amount: u64,
+}
+
+/// Event emitted when the manager withdraws tokens from a treasury vault.
+///
+/// This event contains the mint, the token account receiving the tokens and the amount sent.
+#[event]
+pub struct TreasuryWithdrawn {
+    pub mint: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+}
+
+/// Event emitted when the emission cap is changed.
+///
+/// This event contains the new emission cap.
+#[event]
+pub struct EmissionCapChanged {
+    pub emission_cap: u64,
+}
+
+/// Event emitted when the mint authority of a mint is handed to the program.
+///
+/// This event contains the mint and the previous mint authority.
+#[event]
+pub struct MintAuthorityAccepted {
+    pub mint: Pubkey,
+    pub previous_authority: Pubkey,
+}
+
+/// Event emitted when the program hands the mint authority of a mint back.
+///
+/// This event contains the mint and the new mint authority.
+#[event]
+pub struct MintAuthorityReleased {
+    pub mint: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+/// Event emitted when the emission schedule is changed.
+///
+/// This event contains the new emission schedule.
+#[event]
+pub struct EmissionScheduleChanged {
+    pub emission_schedule: EmissionSchedule,
+}
+
+/// Event emitted when the manager multisig is changed.
+///
+/// This event contains the new members and threshold, both empty in single-key mode.
+#[event]
+pub struct ManagerMultisigChanged {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+/// Event emitted when a manager proposal is created.
+///
+/// This event contains the number of the proposal, the member who created it and the proposed action.
+#[event]
+pub struct ProposalCreated {
+    pub proposal_nr: u64,
+    pub proposer: Pubkey,
+    pub action: ManagerAction,
+}
+
+/// Event emitted when a member approves a manager proposal.
+///
+/// This event contains the number of the proposal, the member and the approvals so far.
+#[event]
+pub struct ProposalApproved {
+    pub proposal_nr: u64,
+    pub member: Pubkey,
+    pub approvals: u8,
+}
+
+/// Event emitted when a manager proposal is executed.
+///
+/// This event contains the number of the proposal.
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_nr: u64,
+}
+
+/// Event emitted when a role is granted.
+///
+/// This event contains the role and the member it is granted to.
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub member: Pubkey,
+}
+
+/// Event emitted when a role is revoked.
+///
+/// This event contains the role and the member it is revoked from.
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub member: Pubkey,
+}
+
+/// Event emitted when the agent quorum is changed.
+///
+/// This event contains the new quorum agents and quorum, both empty in single-agent mode.
+#[event]
+pub struct AgentQuorumChanged {
+    pub agents: Vec<Pubkey>,
+    pub quorum: u8,
+}
+
+/// Event emitted when a quorum agent submits a root diverging from the epoch.
+///
+/// This event contains the epoch number, the agent, the submitted root and mint, and those
+/// of the epoch.
+#[event]
+pub struct EpochRootDiverged {
+    pub epoch_nr: u64,
+    pub agent: Pubkey,
+    pub root: [u8; 32],
+    pub expected_root: [u8; 32],
+    pub mint: Pubkey,
+    pub expected_mint: Pubkey,
+}
+
+/// Event emitted when enough quorum agents submitted the root of an epoch for it to be approved.
+///
+/// This event contains the epoch number and its root.
+#[event]
+pub struct EpochQuorumReached {
+    pub epoch_nr: u64,
+    pub root: [u8; 32],
+}
+
+/// Event emitted when a timelock operation is queued.
+///
+/// This event contains the number of the operation, the queued action and its eta.
+#[event]
+pub struct TimelockOperationQueued {
+    pub operation_nr: u64,
+    pub action: TimelockAction,
+    pub eta: i64,
+}
+
+/// Event emitted when a timelock operation is executed.
+///
+/// This event contains the number of the operation.
+#[event]
+pub struct TimelockOperationExecuted {
+    pub operation_nr: u64,
+}
+
+/// Event emitted when the manager cancels a timelock operation.
+///
+/// This event contains the number of the operation.
+#[event]
+pub struct TimelockOperationCancelled {
+    pub operation_nr: u64,
+}
+
+/// Event emitted when the timelock delay is changed.
+///
+/// This event contains the new delay, in seconds, 0 disabling the timelock.
+#[event]
+pub struct TimelockDelayChanged {
+    pub delay: i64,
 }
@@ -1,21 +1,115 @@
+mod accept_agent;
 mod accept_manager;
+mod accept_mint_authority;
 mod add_epoch;
+mod add_epoch_mint;
 mod approve_epoch;
+mod approve_mint_epoch;
+mod approve_native_epoch;
+mod approve_proposal;
+mod cancel_proposed_agent;
+mod cancel_proposed_manager;
+mod cancel_timelock_operation;
 mod change_agent;
 mod claim;
+mod claim_cumulative;
+mod claim_many;
+mod claim_native;
+mod claim_with_authorization;
+mod close_claim_status;
+mod close_epoch;
+mod close_native_epoch;
 mod correct_epoch;
+mod create_proposal;
+mod deposit_treasury;
+mod execute_timelock_operation;
+mod fund_epoch;
+mod fund_epoch_mint;
+mod grant_role;
+mod init_claim_bitmap;
 mod initialize;
 mod pause;
+mod pause_operations;
+mod propose_agent;
 mod propose_manager;
+mod queue_timelock_operation;
+mod reject_epoch;
+mod release_mint_authority;
+mod revoke_role;
+mod rollover_epoch;
+mod set_agent_quorum;
+mod set_challenge_window;
+mod set_claim_window;
+mod set_distribution_mode;
+mod set_emission_cap;
+mod set_emission_schedule;
+mod set_funder;
+mod set_guardian;
+mod set_manager_multisig;
+mod set_treasury;
+mod submit_epoch_root;
+mod sweep_epoch;
+mod sweep_native_epoch;
 mod unpause;
+mod unpause_operations;
+mod veto_epoch;
+mod veto_native_epoch;
+mod withdraw_treasury;
 
+pub use accept_agent::*;
 pub use accept_manager::*;
+pub use accept_mint_authority::*;
 pub use add_epoch::*;
+pub use add_epoch_mint::*;
 pub use approve_epoch::*;
+pub use approve_mint_epoch::*;
+pub use approve_native_epoch::*;
+pub use approve_proposal::*;
+pub use cancel_proposed_agent::*;
+pub use cancel_proposed_manager::*;
+pub use cancel_timelock_operation::*;
 pub use change_agent::*;
 pub use claim::*;
+pub use claim_cumulative::*;
+pub use claim_many::*;
+pub use claim_native::*;
+pub use claim_with_authorization::*;
+pub use close_claim_status::*;
+pub use close_epoch::*;
+pub use close_native_epoch::*;
 pub use correct_epoch::*;
+pub use create_proposal::*;
+pub use deposit_treasury::*;
+pub use execute_timelock_operation::*;
+pub use fund_epoch::*;
+pub use fund_epoch_mint::*;
+pub use grant_role::*;
+pub use init_claim_bitmap::*;
 pub use initialize::*;
 pub use pause::*;
+pub use pause_operations::*;
+pub use propose_agent::*;
 pub use propose_manager::*;
+pub use queue_timelock_operation::*;
+pub use reject_epoch::*;
+pub use release_mint_authority::*;
+pub use revoke_role::*;
+pub use rollover_epoch::*;
+pub use set_agent_quorum::*;
+pub use set_challenge_window::*;
+pub use set_claim_window::*;
+pub use set_distribution_mode::*;
+pub use set_emission_cap::*;
+pub use set_emission_schedule::*;
+pub use set_funder::*;
+pub use set_guardian::*;
+pub use set_manager_multisig::*;
+pub use set_treasury::*;
+pub use submit_epoch_root::*;
+pub use sweep_epoch::*;
+pub use sweep_native_epoch::*;
 pub use unpause::*;
+pub use unpause_operations::*;
+pub use veto_epoch::*;
+pub use veto_native_epoch::*;
+pub use withdraw_treasury::*;
@@ -0,0 +1,38 @@
+use crate::errors::ErrorCode;
+use crate::events::AgentChanged;
+use crate::state::{RewardsAccount, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::accept_agent] accounts.
+#[derive(Accounts)]
+pub struct AcceptAgent<'info> {
+    #[account(mut)]
+    proposed_agent: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(mut, has_one = proposed_agent @ ErrorCode::Unauthorized)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+}
+
+/// accept agent handler.
+pub fn accept_agent_handler(ctx: Context<AcceptAgent>) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    let new_agent = ctx.accounts.proposed_agent.key();
+
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    // The replaced agent keeps submitting during the overlap period.
+    rewards_account.previous_agent = rewards_account.agent;
+    rewards_account.previous_agent_until =
+        Clock::get()?.unix_timestamp + rewards_account.proposed_agent_overlap;
+    rewards_account.agent = new_agent;
+    rewards_account.proposed_agent = Pubkey::default();
+    rewards_account.proposed_agent_overlap = 0;
+
+    emit!(AgentChanged { new_agent });
+
+    Ok(())
+}
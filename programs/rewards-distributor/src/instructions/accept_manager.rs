@@ -1,6 +1,6 @@
 use crate::errors::ErrorCode;
 use crate::events::ManagerUpdated;
-use crate::state::RewardsAccount;
+use crate::state::{RewardsAccount, PAUSE_ADMIN};
 use anchor_lang::prelude::*;
 
 /// [rewards_distributor::accpet_manager] accounts.
@@ -20,9 +20,12 @@ pub struct AcceptManager<'info> {
 /// set accept manager handler.
 pub fn accept_manager_handler(ctx: Context<AcceptManager>) -> Result<()> {
     let rewards_account = &mut ctx.accounts.rewards_account;
-    let new_manager = *&ctx.accounts.proposed_manager.key();
+    let new_manager = ctx.accounts.proposed_manager.key();
 
-    require!(!rewards_account.is_paused, ErrorCode::ShouldNotBePaused);
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
 
     rewards_account.manager = new_manager;
     rewards_account.proposed_manager = Pubkey::new_from_array([0; 32]);
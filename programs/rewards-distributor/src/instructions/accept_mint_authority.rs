@@ -0,0 +1,67 @@
+use crate::errors::ErrorCode;
+use crate::events::MintAuthorityAccepted;
+use crate::state::{RewardsAccount, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, spl_token_2022::instruction::AuthorityType, Mint, SetAuthority, TokenInterface,
+};
+
+/// [rewards_distributor::accept_mint_authority] accounts.
+#[derive(Accounts)]
+pub struct AcceptMintAuthority<'info> {
+    /// The [RewardsAccount]
+    #[account(has_one = manager @ ErrorCode::Unauthorized)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// current manager of the program, and current mint authority of the mint.
+    pub manager: Signer<'info>,
+
+    /// CHECK: PDA becoming the mint authority of the mint.
+    #[account(
+        seeds = [
+             b"MintAuthority".as_ref(),
+             rewards_account.key().as_ref()
+        ],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// accept mint authority handler.
+pub fn accept_mint_authority_handler(ctx: Context<AcceptMintAuthority>) -> Result<()> {
+    require!(
+        !ctx.accounts.rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    // The manager must hold the mint authority to hand it over.
+    require!(
+        ctx.accounts.mint_account.mint_authority == Some(ctx.accounts.manager.key()).into(),
+        ErrorCode::InvalidMintAuthority
+    );
+
+    token_interface::set_authority(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.manager.to_account_info(),
+                account_or_mint: ctx.accounts.mint_account.to_account_info(),
+            },
+        ),
+        AuthorityType::MintTokens,
+        Some(ctx.accounts.mint_authority.key()),
+    )?;
+
+    emit!(MintAuthorityAccepted {
+        mint: ctx.accounts.mint_account.key(),
+        previous_authority: ctx.accounts.manager.key(),
+    });
+
+    Ok(())
+}
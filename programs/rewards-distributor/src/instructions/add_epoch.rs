@@ -1,16 +1,21 @@
 use crate::errors::ErrorCode;
 use crate::events::EpochCreated;
-use crate::state::{EpochAccount, RewardsAccount};
+use crate::state::{
+    DistributionMode, EpochAccount, RewardsAccount, Role, RoleGrant, RootSubmission,
+    MAX_METADATA_URI_LEN, PAUSE_SUBMISSIONS,
+};
+use crate::utils::token;
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 
 /// [rewards_distributor::add_epoch] accounts.
 #[derive(Accounts)]
+#[instruction(bump: u8, root: [u8; 32], max_total_claim: u64, max_num_nodes: u64, metadata_uri: String)]
 pub struct AddEpoch<'info> {
     /// The [RewardsAccount].
     #[account(
         mut,
-        has_one = agent @ ErrorCode::Unauthorized,
+        constraint = rewards_account.has_role(&agent.key(), Role::Submitter, role_grant.as_deref()) @ ErrorCode::Unauthorized,
     )]
     pub rewards_account: Account<'info, RewardsAccount>,
 
@@ -24,12 +29,13 @@ pub struct AddEpoch<'info> {
         ],
         bump,
         payer = agent,
-        space = 8 + EpochAccount::INIT_SPACE
+        // In quorum mode, adding the epoch submits its root.
+        space = EpochAccount::space(&metadata_uri, 0, 0, usize::from(rewards_account.agent_quorum > 0))
     )]
     pub current_epoch_account: Account<'info, EpochAccount>,
 
-    /// The mint to distribute.
-    pub mint: Account<'info, Mint>,
+    /// The mint to distribute, `None` for an epoch distributing native SOL.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
 
     /// current manager of the program.
     #[account(mut)]
@@ -37,20 +43,56 @@ pub struct AddEpoch<'info> {
 
     /// The [System] program.
     pub system_program: Program<'info, System>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
 }
 
 /// set add epoch handler.
-pub fn add_epoch_handler(ctx: Context<AddEpoch>, bump: u8, root: [u8; 32]) -> Result<()> {
+pub fn add_epoch_handler(
+    ctx: Context<AddEpoch>,
+    bump: u8,
+    root: [u8; 32],
+    max_total_claim: u64,
+    max_num_nodes: u64,
+    metadata_uri: String,
+    distribution_hash: [u8; 32],
+) -> Result<()> {
     let rewards_account = &mut ctx.accounts.rewards_account;
     let current_epoch_account = &mut ctx.accounts.current_epoch_account;
 
-    require!(!rewards_account.is_paused, ErrorCode::ShouldNotBePaused);
+    require!(
+        !rewards_account.is_paused(PAUSE_SUBMISSIONS),
+        ErrorCode::ShouldNotBePaused
+    );
 
     require!(
         rewards_account.current_approved_epoch == rewards_account.current_epoch_nr,
         ErrorCode::PreviousEpochIsNotApproved
     );
 
+    require!(
+        max_total_claim > 0 && max_num_nodes > 0,
+        ErrorCode::InvalidEpochTotals
+    );
+
+    require!(
+        metadata_uri.len() <= MAX_METADATA_URI_LEN,
+        ErrorCode::MetadataUriTooLong
+    );
+
+    if let Some(mint) = &ctx.accounts.mint {
+        token::assert_supported_mint(&mint.to_account_info())?;
+    }
+
+    // Native SOL epochs cannot carry their balance over in cumulative mode.
+    require!(
+        ctx.accounts.mint.is_some()
+            || rewards_account.distribution_mode == DistributionMode::PerEpoch,
+        ErrorCode::InvalidDistributionMode
+    );
+
     // get current epoch number from rewards data account
     let current_epoch_nr = rewards_account.current_epoch_nr;
 
@@ -59,11 +101,37 @@ pub fn add_epoch_handler(ctx: Context<AddEpoch>, bump: u8, root: [u8; 32]) -> Re
     // set epoch data
     current_epoch_account.epoch_nr = current_epoch_nr + 1;
     current_epoch_account.is_approved = false;
-    current_epoch_account.hash = root.clone();
+    current_epoch_account.hash = root;
     current_epoch_account.bump = bump;
-    current_epoch_account.mint = ctx.accounts.mint.key();
+    current_epoch_account.mint = ctx
+        .accounts
+        .mint
+        .as_ref()
+        .map(|mint| mint.key())
+        .unwrap_or_default();
+    current_epoch_account.is_native = ctx.accounts.mint.is_none();
     current_epoch_account.total_amount_claimed = 0;
     current_epoch_account.num_nodes_claimed = 0;
+    current_epoch_account.max_total_claim = max_total_claim;
+    current_epoch_account.max_num_nodes = max_num_nodes;
+    current_epoch_account.creator = ctx.accounts.agent.key();
+    current_epoch_account.distribution_hash = distribution_hash;
+    current_epoch_account.metadata_uri = metadata_uri.clone();
+
+    // In quorum mode, adding the epoch submits its root.
+    if rewards_account.agent_quorum > 0 {
+        let mint = current_epoch_account.mint;
+        current_epoch_account.submit_root(
+            rewards_account,
+            RootSubmission {
+                agent: ctx.accounts.agent.key(),
+                root,
+                max_total_claim,
+                max_num_nodes,
+                mint,
+            },
+        );
+    }
 
     // Get the current Unix timestamp.
     let timestamp = Clock::get()?.unix_timestamp;
@@ -74,7 +142,11 @@ pub fn add_epoch_handler(ctx: Context<AddEpoch>, bump: u8, root: [u8; 32]) -> Re
         hash: root,
         timestamp,
         agent: ctx.accounts.agent.key(),
-        mint: ctx.accounts.mint.key(),
+        mint: current_epoch_account.mint,
+        max_total_claim,
+        max_num_nodes,
+        metadata_uri,
+        distribution_hash,
     });
 
     Ok(())
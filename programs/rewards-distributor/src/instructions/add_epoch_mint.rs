@@ -0,0 +1,98 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochMintAdded;
+use crate::state::{
+    DistributionMode, EpochAccount, EpochMint, RewardsAccount, Role, RoleGrant, MAX_BONUS_MINTS,
+    PAUSE_SUBMISSIONS,
+};
+use crate::utils::token;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+/// [rewards_distributor::add_epoch_mint] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct AddEpochMint<'info> {
+    /// The [RewardsAccount]
+    #[account(
+        constraint = rewards_account.has_role(&agent.key(), Role::Submitter, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump,
+        realloc = epoch_account.space_after_bonus_mint(),
+        realloc::payer = agent,
+        realloc::zero = false,
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// The mint to distribute on top of the primary mint.
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// current agent of the program.
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+/// add epoch mint handler.
+pub fn add_epoch_mint_handler(ctx: Context<AddEpochMint>, epoch_nr: u64) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_SUBMISSIONS),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    // Bonus vaults are not carried over between epochs.
+    require!(
+        rewards_account.distribution_mode == DistributionMode::PerEpoch,
+        ErrorCode::InvalidDistributionMode
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(
+        !epoch_account.is_approved,
+        ErrorCode::EpochShouldNotBeApproved
+    );
+
+    let mint = ctx.accounts.mint.key();
+    token::assert_supported_mint(&ctx.accounts.mint.to_account_info())?;
+    require!(
+        epoch_account.mint_index_of(&mint).is_none(),
+        ErrorCode::DuplicateEpochMint
+    );
+    require!(
+        epoch_account.bonus_mints.len() < MAX_BONUS_MINTS,
+        ErrorCode::TooManyEpochMints
+    );
+
+    epoch_account.bonus_mints.push(EpochMint {
+        mint,
+        funded_amount: 0,
+        total_amount_claimed: 0,
+        is_swept: false,
+    });
+    // The manager commits to the mint set through the correction count.
+    epoch_account.correction_count += 1;
+
+    emit!(EpochMintAdded {
+        epoch_nr,
+        mint,
+        mint_index: epoch_account.bonus_mints.len() as u8,
+        correction_count: epoch_account.correction_count,
+    });
+
+    Ok(())
+}
@@ -1,16 +1,22 @@
 use crate::errors::ErrorCode;
 use crate::events::EpochApproved;
-use crate::state::{EpochAccount, RewardsAccount};
+use crate::state::{
+    ApprovalKind, DistributionMode, EpochAccount, FundingSource, ManagerAction, ManagerProposal,
+    RewardsAccount, Role, RoleGrant, PAUSE_APPROVALS,
+};
+use crate::utils::token;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
 
 /// [rewards_distributor::approve_epoch] accounts.
 #[derive(Accounts)]
 #[instruction( epoch_nr: u64)]
 pub struct ApproveEpoch<'info> {
     /// The [RewardsAccount]
-    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    #[account(mut)]
     pub rewards_account: Account<'info, RewardsAccount>,
 
     /// The [EpochAccount]
@@ -24,41 +30,113 @@ pub struct ApproveEpoch<'info> {
     )]
     pub epoch_account: Account<'info, EpochAccount>,
 
-    /// current manager of the program.
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
     #[account(mut)]
     pub manager: Signer<'info>,
 
-    /// Epoch ATA
+    /// [ManagerProposal] approving the epoch, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// Epoch ATA, which already exists when a vetoed epoch is approved again.
     #[account(
-        init,
+        init_if_needed,
         payer = manager,
         associated_token::mint = mint_account,
         associated_token::authority = epoch_account,
+        associated_token::token_program = token_program,
     )]
-    pub epoch_token_account: Account<'info, TokenAccount>,
+    pub epoch_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// Manager ATA
+    /// Manager ATA, funding the epoch when no treasury vault is passed.
     #[account(mut)]
-    pub manager_token_account: Account<'info, TokenAccount>,
+    pub manager_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury vault of the mint, funding the epoch instead of the manager when passed.
+    #[account(
+        mut,
+        seeds = [
+             b"TreasuryVault".as_ref(),
+             rewards_account.key().as_ref(),
+             mint_account.key().as_ref()
+        ],
+        bump,
+    )]
+    pub treasury_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub mint_account: Account<'info, Mint>,
+    pub mint_account: InterfaceAccount<'info, Mint>,
 
-    /// SPL [Token] program.
-    pub token_program: Program<'info, Token>,
+    /// The previous [EpochAccount], only needed in [DistributionMode::Cumulative].
+    #[account(
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.saturating_sub(1).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub previous_epoch_account: Option<Account<'info, EpochAccount>>,
+
+    /// Previous epoch ATA, only needed in [DistributionMode::Cumulative].
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = previous_epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub previous_epoch_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// Associated [Token] program.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The [System] program.
     pub system_program: Program<'info, System>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
 }
 
-pub fn approve_epoch_handler(ctx: Context<ApproveEpoch>, epoch_nr: u64, amount: u64) -> Result<()> {
+pub fn approve_epoch_handler(
+    ctx: Context<ApproveEpoch>,
+    epoch_nr: u64,
+    amount: u64,
+    expected_root: [u8; 32],
+    expected_correction_count: u64,
+) -> Result<()> {
+    // The treasury vault funds the epoch if passed, the manager otherwise.
+    let funding_source = if ctx.accounts.treasury_vault.is_some() {
+        FundingSource::TreasuryVault
+    } else {
+        FundingSource::Approver
+    };
+
     let rewards_account = &mut ctx.accounts.rewards_account;
-    require!(!rewards_account.is_paused, ErrorCode::ShouldNotBePaused);
+    rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        ctx.accounts.role_grant.as_deref(),
+        Role::Approver,
+        &ManagerAction::ApproveEpoch {
+            epoch_nr,
+            amount,
+            expected_root,
+            expected_correction_count,
+            kind: ApprovalKind::Token,
+            funding_source,
+        },
+    )?;
     require!(
-        epoch_nr == rewards_account.current_epoch_nr,
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+    // Epochs are approved in order, including when a vetoed epoch is approved again.
+    require!(
+        epoch_nr == rewards_account.current_approved_epoch + 1,
         ErrorCode::InvalidEpochNr
     );
 
@@ -71,22 +149,154 @@ pub fn approve_epoch_handler(ctx: Context<ApproveEpoch>, epoch_nr: u64, amount:
         ErrorCode::InvalidMintAccount
     );
 
+    require!(!epoch_account.is_rejected, ErrorCode::EpochRejected);
+    require!(
+        rewards_account.has_root_quorum(epoch_account),
+        ErrorCode::AgentQuorumNotReached
+    );
+
+    // The manager approves the epoch as reviewed, it must not have been corrected since.
+    require!(
+        epoch_account.hash == expected_root
+            && epoch_account.correction_count == expected_correction_count,
+        ErrorCode::EpochChangedSinceReview
+    );
+
+    // The manager confirms the total declared by the agent, in base units.
+    require!(
+        amount == epoch_account.max_total_claim,
+        ErrorCode::EpochTotalMismatch
+    );
+
     epoch_account.is_approved = true;
+    epoch_account.is_mint_mode = false;
+    epoch_account.funded_amount = amount;
+    epoch_account.approver = ctx.accounts.manager.key();
+    epoch_account.funding_source = funding_source;
 
-    // Invoke the transfer instruction on the token program
-    transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.manager_token_account.to_account_info(),
-                to: ctx.accounts.epoch_token_account.to_account_info(),
-                authority: ctx.accounts.manager.to_account_info(),
-            },
-        ),
-        amount * 10u64.pow(ctx.accounts.mint_account.decimals as u32), // Transfer amount, adjust for decimals
+    // Claims open once the challenge window is over, until the end of the claim window, if any.
+    epoch_account.schedule_claims(
+        Clock::get()?.unix_timestamp,
+        rewards_account.challenge_window,
+        rewards_account.claim_window,
+    );
+
+    let mint_info = ctx.accounts.mint_account.to_account_info();
+    let decimals = ctx.accounts.mint_account.decimals;
+
+    // In cumulative mode the latest epoch pays everything still owed, so the
+    // unclaimed balance of the previous epoch moves to the new vault.
+    let mut carried_over = 0;
+    if rewards_account.distribution_mode == DistributionMode::Cumulative && epoch_nr > 1 {
+        let (Some(previous_epoch_account), Some(previous_epoch_token_account)) = (
+            &ctx.accounts.previous_epoch_account,
+            &ctx.accounts.previous_epoch_token_account,
+        ) else {
+            return err!(ErrorCode::InvalidPreviousEpoch);
+        };
+        require!(
+            previous_epoch_account.mint == ctx.accounts.mint_account.key(),
+            ErrorCode::InvalidMintAccount
+        );
+
+        let rewards_account_key = rewards_account.key();
+        let seeds = [
+            b"EpochAccount".as_ref(),
+            rewards_account_key.as_ref(),
+            &previous_epoch_account.epoch_nr.to_le_bytes(),
+            &[previous_epoch_account.bump],
+        ];
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: previous_epoch_token_account.to_account_info(),
+                    mint: mint_info.clone(),
+                    to: ctx.accounts.epoch_token_account.to_account_info(),
+                    authority: previous_epoch_account.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds[..]]),
+            previous_epoch_token_account.amount,
+            decimals,
+        )?;
+
+        carried_over = previous_epoch_token_account.amount
+            - token::transfer_fee(&mint_info, previous_epoch_token_account.amount)?;
+    }
+    epoch_account.carried_over = carried_over;
+
+    // The vault must end up holding exactly the declared total, the manager funds what
+    // was neither carried nor rolled over plus the transfer fees, if any.
+    let net_amount = amount
+        .checked_sub(carried_over)
+        .and_then(|amount| amount.checked_sub(epoch_account.rolled_over_amount))
+        .ok_or(ErrorCode::EpochOverfunded)?;
+    let gross_amount = token::gross_amount(&mint_info, net_amount)?;
+
+    // What the epoch issues on top of the carried over balance is capped by the emission schedule.
+    rewards_account.assert_within_emission_schedule(
+        epoch_nr,
+        &ctx.accounts.mint_account.key(),
+        net_amount,
     )?;
+    epoch_account.issued_amount = net_amount;
+
+    // Invoke the transfer instruction on the token program, from the treasury
+    // vault if passed, from the manager otherwise.
+    if let (Some(treasury_vault), Some(treasury_vault_bump)) =
+        (&ctx.accounts.treasury_vault, ctx.bumps.treasury_vault)
+    {
+        let rewards_account_key = rewards_account.key();
+        let mint_key = ctx.accounts.mint_account.key();
+        let seeds = [
+            b"TreasuryVault".as_ref(),
+            rewards_account_key.as_ref(),
+            mint_key.as_ref(),
+            &[treasury_vault_bump],
+        ];
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: treasury_vault.to_account_info(),
+                    mint: mint_info,
+                    to: ctx.accounts.epoch_token_account.to_account_info(),
+                    authority: treasury_vault.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds[..]]),
+            gross_amount,
+            decimals,
+        )?;
+    } else {
+        let Some(manager_token_account) = &ctx.accounts.manager_token_account else {
+            return err!(ErrorCode::MissingFundingSource);
+        };
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: manager_token_account.to_account_info(),
+                    mint: mint_info,
+                    to: ctx.accounts.epoch_token_account.to_account_info(),
+                    authority: ctx.accounts.manager.to_account_info(),
+                },
+            ),
+            gross_amount,
+            decimals,
+        )?;
+    }
 
-    emit!(EpochApproved { epoch_nr });
+    emit!(EpochApproved {
+        epoch_nr,
+        root: epoch_account.hash,
+        amount: gross_amount,
+        net_amount,
+    });
 
     Ok(())
 }
@@ -0,0 +1,174 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochApproved;
+use crate::state::{
+    ApprovalKind, DistributionMode, EpochAccount, FundingSource, ManagerAction, ManagerProposal,
+    RewardsAccount, Role, RoleGrant, PAUSE_APPROVALS,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// [rewards_distributor::approve_mint_epoch] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct ApproveMintEpoch<'info> {
+    /// The [RewardsAccount]
+    #[account(mut)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] approving the epoch, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// Epoch ATA, which stays empty for the primary mint but identifies the epoch in claims.
+    #[account(
+        init_if_needed,
+        payer = manager,
+        associated_token::mint = mint_account,
+        associated_token::authority = epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub epoch_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA holding the mint authority of the mint.
+    #[account(
+        seeds = [
+             b"MintAuthority".as_ref(),
+             rewards_account.key().as_ref()
+        ],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        address = epoch_account.mint @ ErrorCode::InvalidMintAccount,
+        constraint = mint_account.mint_authority == Some(mint_authority.key()).into() @ ErrorCode::InvalidMintAuthority
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated [Token] program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+pub fn approve_mint_epoch_handler(
+    ctx: Context<ApproveMintEpoch>,
+    epoch_nr: u64,
+    amount: u64,
+    expected_root: [u8; 32],
+    expected_correction_count: u64,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        ctx.accounts.role_grant.as_deref(),
+        Role::Approver,
+        &ManagerAction::ApproveEpoch {
+            epoch_nr,
+            amount,
+            expected_root,
+            expected_correction_count,
+            kind: ApprovalKind::Mint,
+            funding_source: FundingSource::Approver,
+        },
+    )?;
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+    // Epochs are approved in order, including when a vetoed epoch is approved again.
+    require!(
+        epoch_nr == rewards_account.current_approved_epoch + 1,
+        ErrorCode::InvalidEpochNr
+    );
+    // Minted epochs have no balance to carry over in cumulative mode.
+    require!(
+        rewards_account.distribution_mode == DistributionMode::PerEpoch,
+        ErrorCode::InvalidDistributionMode
+    );
+
+    rewards_account.current_approved_epoch = epoch_nr;
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+
+    require!(!epoch_account.is_rejected, ErrorCode::EpochRejected);
+    // Claims are minted, the rewards rolled over into the vault could never be paid.
+    require!(
+        epoch_account.rolled_over_amount == 0,
+        ErrorCode::EpochOverfunded
+    );
+    require!(
+        rewards_account.has_root_quorum(epoch_account),
+        ErrorCode::AgentQuorumNotReached
+    );
+
+    // The manager approves the epoch as reviewed, it must not have been corrected since.
+    require!(
+        epoch_account.hash == expected_root
+            && epoch_account.correction_count == expected_correction_count,
+        ErrorCode::EpochChangedSinceReview
+    );
+
+    // The manager confirms the total declared by the agent, in base units, which caps
+    // what the claims of the epoch can mint.
+    require!(
+        amount == epoch_account.max_total_claim,
+        ErrorCode::EpochTotalMismatch
+    );
+
+    // Everything the epoch can mint is capped by the emission schedule.
+    rewards_account.assert_within_emission_schedule(
+        epoch_nr,
+        &ctx.accounts.mint_account.key(),
+        amount,
+    )?;
+
+    epoch_account.is_approved = true;
+    epoch_account.is_mint_mode = true;
+    epoch_account.funded_amount = 0;
+    epoch_account.issued_amount = amount;
+    epoch_account.approver = ctx.accounts.manager.key();
+    epoch_account.funding_source = FundingSource::Approver;
+
+    // Claims open once the challenge window is over, until the end of the claim window, if any.
+    epoch_account.schedule_claims(
+        Clock::get()?.unix_timestamp,
+        rewards_account.challenge_window,
+        rewards_account.claim_window,
+    );
+
+    // Nothing is funded, the claims mint the tokens.
+    emit!(EpochApproved {
+        epoch_nr,
+        root: epoch_account.hash,
+        amount: 0,
+        net_amount: 0,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochApproved;
+use crate::state::{
+    ApprovalKind, DistributionMode, EpochAccount, FundingSource, ManagerAction, ManagerProposal,
+    RewardsAccount, Role, RoleGrant, PAUSE_APPROVALS,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+/// [rewards_distributor::approve_native_epoch] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct ApproveNativeEpoch<'info> {
+    /// The [RewardsAccount]
+    #[account(mut)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] approving the epoch, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// Lamport vault of the epoch.
+    #[account(
+        mut,
+        seeds = [
+             b"EpochVault".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+pub fn approve_native_epoch_handler(
+    ctx: Context<ApproveNativeEpoch>,
+    epoch_nr: u64,
+    amount: u64,
+    expected_root: [u8; 32],
+    expected_correction_count: u64,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        ctx.accounts.role_grant.as_deref(),
+        Role::Approver,
+        &ManagerAction::ApproveEpoch {
+            epoch_nr,
+            amount,
+            expected_root,
+            expected_correction_count,
+            kind: ApprovalKind::Native,
+            funding_source: FundingSource::Approver,
+        },
+    )?;
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+    // Epochs are approved in order, including when a vetoed epoch is approved again.
+    require!(
+        epoch_nr == rewards_account.current_approved_epoch + 1,
+        ErrorCode::InvalidEpochNr
+    );
+    require!(
+        rewards_account.distribution_mode == DistributionMode::PerEpoch,
+        ErrorCode::InvalidDistributionMode
+    );
+
+    rewards_account.current_approved_epoch = epoch_nr;
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(epoch_account.is_native, ErrorCode::NotNativeEpoch);
+
+    require!(!epoch_account.is_rejected, ErrorCode::EpochRejected);
+    require!(
+        rewards_account.has_root_quorum(epoch_account),
+        ErrorCode::AgentQuorumNotReached
+    );
+
+    // The manager approves the epoch as reviewed, it must not have been corrected since.
+    require!(
+        epoch_account.hash == expected_root
+            && epoch_account.correction_count == expected_correction_count,
+        ErrorCode::EpochChangedSinceReview
+    );
+
+    // The manager confirms the total declared by the agent, in lamports.
+    require!(
+        amount == epoch_account.max_total_claim,
+        ErrorCode::EpochTotalMismatch
+    );
+
+    epoch_account.is_approved = true;
+    epoch_account.funded_amount = amount;
+    epoch_account.approver = ctx.accounts.manager.key();
+    epoch_account.funding_source = FundingSource::Approver;
+
+    // Claims open once the challenge window is over, until the end of the claim window, if any.
+    epoch_account.schedule_claims(
+        Clock::get()?.unix_timestamp,
+        rewards_account.challenge_window,
+        rewards_account.claim_window,
+    );
+
+    // The manager also pays the rent-exempt minimum of the vault, which claims never touch.
+    let rent = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(ctx.accounts.vault.lamports());
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.manager.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        amount + rent,
+    )?;
+
+    emit!(EpochApproved {
+        epoch_nr,
+        root: epoch_account.hash,
+        amount,
+        net_amount: amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::errors::ErrorCode;
+use crate::state::{ManagerProposal, RewardsAccount};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::approve_proposal] accounts.
+#[derive(Accounts)]
+#[instruction(proposal_nr: u64)]
+pub struct ApproveProposal<'info> {
+    /// The [RewardsAccount].
+    #[account(mut)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [ManagerProposal] to approve.
+    #[account(
+        mut,
+        seeds = [
+            b"ManagerProposal".as_ref(),
+            rewards_account.key().as_ref(),
+            proposal_nr.to_le_bytes().as_ref()
+        ],
+        bump,
+        has_one = rewards_account @ ErrorCode::ProposalMismatch
+    )]
+    pub proposal: Account<'info, ManagerProposal>,
+
+    /// A member of the manager multisig.
+    pub member: Signer<'info>,
+}
+
+/// approve proposal handler.
+pub fn approve_proposal_handler(ctx: Context<ApproveProposal>, _proposal_nr: u64) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    let proposal = &mut ctx.accounts.proposal;
+
+    proposal.approve(rewards_account, &ctx.accounts.member.key())?;
+    rewards_account.execute_proposal(proposal)
+}
@@ -0,0 +1,32 @@
+use crate::errors::ErrorCode;
+use crate::events::ProposedAgentCancelled;
+use crate::state::RewardsAccount;
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::cancel_proposed_agent] accounts.
+#[derive(Accounts)]
+pub struct CancelProposedAgent<'info> {
+    // current manager of the program.
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    rewards_account: Account<'info, RewardsAccount>,
+}
+
+/// cancel proposed agent handler.
+pub fn cancel_proposed_agent_handler(ctx: Context<CancelProposedAgent>) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    let proposed_agent = rewards_account.proposed_agent;
+    require!(
+        proposed_agent != Pubkey::default(),
+        ErrorCode::NoPendingProposal
+    );
+
+    rewards_account.proposed_agent = Pubkey::default();
+    rewards_account.proposed_agent_overlap = 0;
+
+    emit!(ProposedAgentCancelled { proposed_agent });
+
+    Ok(())
+}
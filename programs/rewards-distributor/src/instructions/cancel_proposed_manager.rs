@@ -0,0 +1,31 @@
+use crate::errors::ErrorCode;
+use crate::events::ProposedManagerCancelled;
+use crate::state::RewardsAccount;
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::cancel_proposed_manager] accounts.
+#[derive(Accounts)]
+pub struct CancelProposedManager<'info> {
+    // current manager of the program.
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    rewards_account: Account<'info, RewardsAccount>,
+}
+
+/// cancel proposed manager handler.
+pub fn cancel_proposed_manager_handler(ctx: Context<CancelProposedManager>) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    let proposed_manager = rewards_account.proposed_manager;
+    require!(
+        proposed_manager != Pubkey::default(),
+        ErrorCode::NoPendingProposal
+    );
+
+    rewards_account.proposed_manager = Pubkey::default();
+
+    emit!(ProposedManagerCancelled { proposed_manager });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::errors::ErrorCode;
+use crate::events::TimelockOperationCancelled;
+use crate::state::{RewardsAccount, TimelockOperation};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::cancel_timelock_operation] accounts.
+#[derive(Accounts)]
+#[instruction(operation_nr: u64)]
+pub struct CancelTimelockOperation<'info> {
+    // current manager of the program.
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(has_one = manager @ ErrorCode::Unauthorized)]
+    rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [TimelockOperation] to cancel.
+    #[account(
+        mut,
+        seeds = [
+            b"TimelockOperation".as_ref(),
+            rewards_account.key().as_ref(),
+            operation_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    operation: Account<'info, TimelockOperation>,
+}
+
+/// cancel timelock operation handler.
+pub fn cancel_timelock_operation_handler(
+    ctx: Context<CancelTimelockOperation>,
+    operation_nr: u64,
+) -> Result<()> {
+    let operation = &mut ctx.accounts.operation;
+    require!(
+        operation.is_pending(),
+        ErrorCode::TimelockOperationNotPending
+    );
+
+    operation.is_cancelled = true;
+
+    emit!(TimelockOperationCancelled { operation_nr });
+
+    Ok(())
+}
@@ -1,6 +1,5 @@
 use crate::errors::ErrorCode;
-use crate::events::AgentChanged;
-use crate::state::RewardsAccount;
+use crate::state::{RewardsAccount, Role, RoleGrant, PAUSE_ADMIN};
 use anchor_lang::prelude::*;
 
 /// [rewards_distributor::change_agent] accounts.
@@ -11,17 +10,30 @@ pub struct ChangeAgent<'info> {
     manager: Signer<'info>,
 
     /// The [RewardsAccount].
-    #[account(mut, has_one = manager @ErrorCode::Unauthorized)]
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&manager.key(), Role::ConfigAdmin, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
     rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    role_grant: Option<Account<'info, RoleGrant>>,
 }
 
 pub fn change_agent_handler(ctx: Context<ChangeAgent>, new_agent: Pubkey) -> Result<()> {
     let rewards_account = &mut ctx.accounts.rewards_account;
-    require!(!rewards_account.is_paused, ErrorCode::ShouldNotBePaused);
-
-    rewards_account.agent = new_agent;
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(!rewards_account.is_multisig(), ErrorCode::ProposalRequired);
+    require!(
+        !rewards_account.is_timelocked(),
+        ErrorCode::TimelockRequired
+    );
 
-    emit!(AgentChanged { new_agent });
+    rewards_account.set_agent(new_agent);
 
     Ok(())
 }
@@ -1,10 +1,12 @@
 use crate::errors::ErrorCode;
 use crate::events::ClaimedEvent;
-use crate::state::{ClaimStatus, EpochAccount, RewardsAccount};
-use crate::utils::merkle_proof;
+use crate::state::{
+    ClaimBitmap, ClaimStatus, DistributionMode, EpochAccount, RewardsAccount, PAUSE_CLAIMS,
+};
+use crate::utils::{merkle_proof, token};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 
 /// [rewards_distributor::claim] accounts.
 #[derive(Accounts)]
@@ -20,7 +22,7 @@ pub struct Claim<'info> {
     )]
     pub epoch_account: Account<'info, EpochAccount>,
 
-    /// Status of the claim.
+    /// Status of the claim, for epochs without a [ClaimBitmap].
     #[account(
         init,
         seeds = [
@@ -33,11 +35,15 @@ pub struct Claim<'info> {
         space = 8 + ClaimStatus::LEN,
         payer = receiver
     )]
-    pub claim_status: Account<'info, ClaimStatus>,
+    pub claim_status: Option<Account<'info, ClaimStatus>>,
+
+    /// The [ClaimBitmap] shard tracking `index`, for epochs with a bitmap.
+    #[account(mut)]
+    pub claim_bitmap: Option<AccountLoader<'info, ClaimBitmap>>,
 
     /// Distributor ATA containing the tokens to distribute.
     #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
+    pub from: InterfaceAccount<'info, TokenAccount>,
 
     // Account to send the claimed tokens to.
     #[account(
@@ -47,17 +53,27 @@ pub struct Claim<'info> {
         associated_token::authority = receiver,
         associated_token::token_program = token_program
     )]
-    pub to: Account<'info, TokenAccount>,
+    pub to: InterfaceAccount<'info, TokenAccount>,
 
     /// Who is claiming the tokens.
     #[account(mut, address = to.owner @ ErrorCode::OwnerMismatch)]
     pub receiver: Signer<'info>,
 
     #[account(mut)]
-    pub mint_account: Account<'info, Mint>,
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA holding the mint authority of the mint, only needed for epochs in mint mode.
+    #[account(
+        seeds = [
+            b"MintAuthority".as_ref(),
+            rewards_account.key().as_ref()
+        ],
+        bump
+    )]
+    pub mint_authority: Option<UncheckedAccount<'info>>,
 
-    /// SPL [Token] program.
-    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// Associated [Token] program.
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -72,14 +88,24 @@ pub fn claim_handler(
     index: u64,
     amount: u64,
     proof: Vec<[u8; 32]>,
+    mint_index: u8,
 ) -> Result<()> {
-    let rewards_account = &ctx.accounts.rewards_account;
-    require!(!rewards_account.is_paused, ErrorCode::ShouldNotBePaused);
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_CLAIMS),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(
+        rewards_account.distribution_mode == DistributionMode::PerEpoch,
+        ErrorCode::InvalidDistributionMode
+    );
 
+    let clock = Clock::get()?;
     let epoch_account = &mut ctx.accounts.epoch_account;
-    require!(epoch_account.is_approved, ErrorCode::EpochShouldBeApproved);
-    require!(
-        epoch_account.mint == ctx.accounts.mint_account.key(),
+    epoch_account.assert_claimable(clock.unix_timestamp)?;
+    require_keys_eq!(
+        epoch_account.mint_at(mint_index)?,
+        ctx.accounts.mint_account.key(),
         ErrorCode::InvalidMintAccount
     );
 
@@ -88,14 +114,6 @@ pub fn claim_handler(
     // Ensure the `from` and `to` accounts are different.
     require_keys_neq!(ctx.accounts.from.key(), ctx.accounts.to.key());
 
-    // Get a mutable reference to the claim status account.
-    let claim_status = &mut ctx.accounts.claim_status;
-    require!(
-        // This check is redundant, we should not be able to initialize a claim status account at the same key.
-        !claim_status.is_claimed && claim_status.claimed_at == 0,
-        ErrorCode::DropAlreadyClaimed
-    );
-
     // Get references to the receiver account and the distributor account.
     let receiver_account = &ctx.accounts.receiver;
 
@@ -103,22 +121,29 @@ pub fn claim_handler(
     require!(receiver_account.is_signer, ErrorCode::Unauthorized);
 
     // Verify the merkle proof.
-    let node = anchor_lang::solana_program::keccak::hashv(&[
-        &index.to_le_bytes(),
-        &receiver_account.key().to_bytes(),
-        &amount.to_le_bytes(),
-    ]);
+    let node = merkle_proof::claim_leaf(index, &receiver_account.key(), amount, mint_index);
     require!(
-        merkle_proof::verify(proof, epoch_root, node.0),
+        merkle_proof::verify(proof, epoch_root, node),
         ErrorCode::InvalidProof
     );
 
     // Mark it claimed and send the tokens.
-    claim_status.amount = amount;
-    claim_status.is_claimed = true;
-    let clock = Clock::get()?;
-    claim_status.claimed_at = clock.unix_timestamp;
-    claim_status.receiver = receiver_account.key();
+    track_claim(
+        epoch_account,
+        ctx.accounts.claim_status.as_mut(),
+        ctx.accounts.claim_bitmap.as_ref(),
+        index,
+        ClaimStatus {
+            is_claimed: true,
+            receiver: receiver_account.key(),
+            claimed_at: clock.unix_timestamp,
+            amount,
+            payer: receiver_account.key(),
+        },
+    )?;
+
+    // Update the distributor's total amount claimed and number of nodes claimed.
+    epoch_account.record_claim(mint_index, amount)?;
 
     // Ensure the owner of the `to` account matches the receiver's public key.
     require_keys_eq!(
@@ -127,40 +152,115 @@ pub fn claim_handler(
         ErrorCode::OwnerMismatch
     );
 
-    // Define the seeds for signing the transaction.
     let rewards_account_key = rewards_account.key();
-    let seeds = [
-        b"EpochAccount".as_ref(),
-        rewards_account_key.as_ref(),
-        &epoch_account.epoch_nr.to_le_bytes(),
-        &[epoch_account.bump],
-    ];
-
-    // Invoke the transfer instruction on the token program
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
-                from: ctx.accounts.from.to_account_info(),
-                to: ctx.accounts.to.to_account_info(),
-                authority: epoch_account.to_account_info(),
-            },
-        )
-        .with_signer(&[&seeds[..]]),
-        amount,
-    )?;
+    let net_amount = if epoch_account.is_minted(mint_index) {
+        let (Some(mint_authority), Some(mint_authority_bump)) =
+            (&ctx.accounts.mint_authority, ctx.bumps.mint_authority)
+        else {
+            return err!(ErrorCode::InvalidMintAuthority);
+        };
 
-    // Update the distributor's total amount claimed and number of nodes claimed.
-    epoch_account.total_amount_claimed = epoch_account.total_amount_claimed + amount;
-    epoch_account.num_nodes_claimed += 1;
+        // Minting is also capped globally, across the epochs in mint mode.
+        rewards_account.record_emission(amount)?;
+
+        let seeds = [
+            b"MintAuthority".as_ref(),
+            rewards_account_key.as_ref(),
+            &[mint_authority_bump],
+        ];
+
+        // Mint the amount to the receiver, no transfer fee applies.
+        token_interface::mint_to(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.mint_account.to_account_info(),
+                    to: ctx.accounts.to.to_account_info(),
+                    authority: mint_authority.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds[..]]),
+            amount,
+        )?;
+
+        amount
+    } else {
+        // Define the seeds for signing the transaction.
+        let seeds = [
+            b"EpochAccount".as_ref(),
+            rewards_account_key.as_ref(),
+            &epoch_account.epoch_nr.to_le_bytes(),
+            &[epoch_account.bump],
+        ];
+
+        // Invoke the transfer instruction on the token program
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.from.to_account_info(),
+                    mint: ctx.accounts.mint_account.to_account_info(),
+                    to: ctx.accounts.to.to_account_info(),
+                    authority: epoch_account.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds[..]]),
+            amount,
+            ctx.accounts.mint_account.decimals,
+        )?;
+
+        // The receiver gets the amount minus the transfer fee withheld by the mint, if any.
+        amount - token::transfer_fee(&ctx.accounts.mint_account.to_account_info(), amount)?
+    };
 
     // Emit an event indicating that the claim has been made.
     emit!(ClaimedEvent {
         index,
         receiver: receiver_account.key(),
         amount,
-        epoch_nr: epoch_account.epoch_nr
+        net_amount,
+        epoch_nr: epoch_account.epoch_nr,
+        relayer: None,
     });
 
     Ok(())
 }
+
+/// Marks the leaf at `index` as claimed, in the [ClaimBitmap] for epochs that have one,
+/// or by storing `claim` in the newly created [ClaimStatus] otherwise.
+pub(crate) fn track_claim(
+    epoch_account: &Account<EpochAccount>,
+    claim_status: Option<&mut Account<ClaimStatus>>,
+    claim_bitmap: Option<&AccountLoader<ClaimBitmap>>,
+    index: u64,
+    claim: ClaimStatus,
+) -> Result<()> {
+    match (
+        epoch_account.claim_bitmap_shards > 0,
+        claim_status,
+        claim_bitmap,
+    ) {
+        (false, Some(claim_status), None) => {
+            require!(
+                // This check is redundant, we should not be able to initialize a claim status account at the same key.
+                !claim_status.is_claimed && claim_status.claimed_at == 0,
+                ErrorCode::DropAlreadyClaimed
+            );
+
+            claim_status.set_inner(claim);
+
+            Ok(())
+        }
+        (true, None, Some(claim_bitmap)) => {
+            let claim_bitmap = &mut claim_bitmap.load_mut()?;
+            require_keys_eq!(
+                claim_bitmap.epoch,
+                epoch_account.key(),
+                ErrorCode::InvalidClaimTracking
+            );
+
+            claim_bitmap.set_claimed(index)
+        }
+        _ => err!(ErrorCode::InvalidClaimTracking),
+    }
+}
@@ -0,0 +1,160 @@
+use crate::errors::ErrorCode;
+use crate::events::ClaimedEvent;
+use crate::state::{DistributionMode, EpochAccount, RewardsAccount, UserClaimState, PAUSE_CLAIMS};
+use crate::utils::{merkle_proof, token};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// [rewards_distributor::claim_cumulative] accounts.
+#[derive(Accounts)]
+pub struct ClaimCumulative<'info> {
+    /// The [RewardsAccount].
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The latest approved [EpochAccount].
+    #[account(mut,
+        seeds = [
+            b"EpochAccount".as_ref(),
+            rewards_account.key().as_ref(),
+            rewards_account.current_approved_epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        address = from.owner
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// Amount already paid to the receiver.
+    #[account(
+        init_if_needed,
+        seeds = [
+            b"UserClaimState".as_ref(),
+            rewards_account.key().as_ref(),
+            receiver.key().as_ref()
+        ],
+        bump,
+        space = 8 + UserClaimState::INIT_SPACE,
+        payer = receiver
+    )]
+    pub user_claim_state: Account<'info, UserClaimState>,
+
+    /// Distributor ATA containing the tokens to distribute.
+    #[account(mut)]
+    pub from: InterfaceAccount<'info, TokenAccount>,
+
+    // Account to send the claimed tokens to.
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        associated_token::mint = mint_account,
+        associated_token::authority = receiver,
+        associated_token::token_program = token_program
+    )]
+    pub to: InterfaceAccount<'info, TokenAccount>,
+
+    /// Who is claiming the tokens.
+    #[account(mut, address = to.owner @ ErrorCode::OwnerMismatch)]
+    pub receiver: Signer<'info>,
+
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated [Token] program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// claim cumulative handler.
+pub fn claim_cumulative_handler(
+    ctx: Context<ClaimCumulative>,
+    index: u64,
+    cumulative_amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_CLAIMS),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(
+        rewards_account.distribution_mode == DistributionMode::Cumulative,
+        ErrorCode::InvalidDistributionMode
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    epoch_account.assert_claimable(Clock::get()?.unix_timestamp)?;
+    require!(
+        epoch_account.mint == ctx.accounts.mint_account.key(),
+        ErrorCode::InvalidMintAccount
+    );
+
+    // Ensure the `from` and `to` accounts are different.
+    require_keys_neq!(ctx.accounts.from.key(), ctx.accounts.to.key());
+
+    let receiver = ctx.accounts.receiver.key();
+
+    // Verify the merkle proof, the leaf holds the lifetime entitlement.
+    let node = merkle_proof::claim_leaf(index, &receiver, cumulative_amount, 0);
+    require!(
+        merkle_proof::verify(proof, epoch_account.hash, node),
+        ErrorCode::InvalidProof
+    );
+
+    let user_claim_state = &mut ctx.accounts.user_claim_state;
+    let amount = cumulative_amount
+        .checked_sub(user_claim_state.claimed_amount)
+        .filter(|amount| *amount > 0)
+        .ok_or(ErrorCode::NothingToClaim)?;
+
+    user_claim_state.receiver = receiver;
+    user_claim_state.claimed_amount = cumulative_amount;
+    user_claim_state.last_claimed_epoch = epoch_account.epoch_nr;
+
+    // Update the distributor's total amount claimed and number of nodes claimed.
+    epoch_account.record_claim(0, amount)?;
+
+    // Define the seeds for signing the transaction.
+    let rewards_account_key = rewards_account.key();
+    let seeds = [
+        b"EpochAccount".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_account.epoch_nr.to_le_bytes(),
+        &[epoch_account.bump],
+    ];
+
+    // The receiver gets the amount minus the transfer fee withheld by the mint, if any.
+    let net_amount =
+        amount - token::transfer_fee(&ctx.accounts.mint_account.to_account_info(), amount)?;
+
+    // Invoke the transfer instruction on the token program
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.from.to_account_info(),
+                mint: ctx.accounts.mint_account.to_account_info(),
+                to: ctx.accounts.to.to_account_info(),
+                authority: epoch_account.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+        ctx.accounts.mint_account.decimals,
+    )?;
+
+    // Emit an event indicating that the claim has been made.
+    emit!(ClaimedEvent {
+        index,
+        receiver,
+        amount,
+        net_amount,
+        epoch_nr: epoch_account.epoch_nr,
+        relayer: None,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,328 @@
+use crate::errors::ErrorCode;
+use crate::events::ClaimedEvent;
+use crate::instructions::track_claim;
+use crate::state::{
+    ClaimBitmap, ClaimStatus, DistributionMode, EpochAccount, RewardsAccount, PAUSE_CLAIMS,
+};
+use crate::utils::{account, merkle_proof, token};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// Number of `remaining_accounts` expected for each [ClaimEntry]:
+/// the [EpochAccount], its ATA and the [ClaimStatus] to create or the [ClaimBitmap] shard.
+pub const ACCOUNTS_PER_CLAIM_ENTRY: usize = 3;
+
+/// A single claim of a [rewards_distributor::claim_many] batch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimEntry {
+    /// The epoch number to claim from.
+    pub epoch_nr: u64,
+
+    /// Index of the claim in the Merkle tree of the epoch.
+    pub index: u64,
+
+    /// Amount to be claimed by the user.
+    pub amount: u64,
+
+    /// Merkle proof for verifying the claim.
+    pub proof: Vec<[u8; 32]>,
+
+    /// Index of the mint of the epoch paid by the leaf, 0 for the primary mint.
+    pub mint_index: u8,
+}
+
+/// [rewards_distributor::claim_many] accounts.
+///
+/// For every entry, `remaining_accounts` must contain, in order, the
+/// [EpochAccount] (writable), its ATA of the mint (writable) and either the [ClaimStatus]
+/// PDA to create or, for epochs with a bitmap, the [ClaimBitmap] shard of the
+/// index (writable).
+#[derive(Accounts)]
+pub struct ClaimMany<'info> {
+    /// The [RewardsAccount].
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    // Account to send the claimed tokens to.
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        associated_token::mint = mint_account,
+        associated_token::authority = receiver,
+        associated_token::token_program = token_program
+    )]
+    pub to: InterfaceAccount<'info, TokenAccount>,
+
+    /// Who is claiming the tokens.
+    #[account(mut, address = to.owner @ ErrorCode::OwnerMismatch)]
+    pub receiver: Signer<'info>,
+
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated [Token] program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// An epoch vault and the total owed from it by the batch.
+struct VaultPayout<'info> {
+    epoch_account: Account<'info, EpochAccount>,
+    from: AccountInfo<'info>,
+    amount: u64,
+
+    /// Amount, and amount received by the receiver, not yet attributed to an entry.
+    unshared_amount: u64,
+    unshared_net_amount: u64,
+}
+
+impl VaultPayout<'_> {
+    /// Attributes its share of the net amount received to an entry of `amount`, the last
+    /// entry of the vault getting the rounding remainder.
+    fn share_net_amount(&mut self, amount: u64) -> u64 {
+        let net_amount = match self.unshared_amount {
+            unshared_amount if unshared_amount == amount => self.unshared_net_amount,
+            unshared_amount => {
+                (amount as u128 * self.unshared_net_amount as u128 / unshared_amount as u128) as u64
+            }
+        };
+        self.unshared_amount -= amount;
+        self.unshared_net_amount -= net_amount;
+
+        net_amount
+    }
+}
+
+/// claim many handler.
+pub fn claim_many_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>,
+    entries: Vec<ClaimEntry>,
+) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_CLAIMS),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(
+        rewards_account.distribution_mode == DistributionMode::PerEpoch,
+        ErrorCode::InvalidDistributionMode
+    );
+
+    require!(!entries.is_empty(), ErrorCode::InvalidRemainingAccounts);
+    require!(
+        ctx.remaining_accounts.len() == entries.len() * ACCOUNTS_PER_CLAIM_ENTRY,
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let receiver = ctx.accounts.receiver.key();
+    let mint_info = ctx.accounts.mint_account.to_account_info();
+    let claimed_at = Clock::get()?.unix_timestamp;
+    let mut payouts: Vec<VaultPayout<'info>> = Vec::new();
+    let mut positions = Vec::with_capacity(entries.len());
+
+    for (i, (entry, accounts)) in entries
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_CLAIM_ENTRY))
+        .enumerate()
+    {
+        // The error names the position of the failing entry along with its original error.
+        let position = claim_entry(&ctx, entry, accounts, &mut payouts, claimed_at)
+            .map_err(|err| error!(ErrorCode::ClaimEntryFailed).with_values((i, err)))?;
+        positions.push(position);
+    }
+
+    // Tokens of different epochs sit in different vaults, each owned by its
+    // own epoch PDA, so the batch does one transfer per vault.
+    let rewards_account_key = rewards_account.key();
+    for payout in payouts.iter_mut() {
+        let epoch_nr_bytes = payout.epoch_account.epoch_nr.to_le_bytes();
+        let seeds = [
+            b"EpochAccount".as_ref(),
+            rewards_account_key.as_ref(),
+            &epoch_nr_bytes,
+            &[payout.epoch_account.bump],
+        ];
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: payout.from.clone(),
+                    mint: mint_info.clone(),
+                    to: ctx.accounts.to.to_account_info(),
+                    authority: payout.epoch_account.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds[..]]),
+            payout.amount,
+            ctx.accounts.mint_account.decimals,
+        )?;
+
+        // The fee is withheld once per transfer, and shared by its entries.
+        payout.unshared_amount = payout.amount;
+        payout.unshared_net_amount =
+            payout.amount - token::transfer_fee(&mint_info, payout.amount)?;
+
+        payout.epoch_account.exit(&crate::ID)?;
+    }
+
+    for (entry, position) in entries.iter().zip(positions) {
+        let payout = &mut payouts[position];
+
+        emit!(ClaimedEvent {
+            index: entry.index,
+            receiver,
+            amount: entry.amount,
+            net_amount: payout.share_net_amount(entry.amount),
+            epoch_nr: payout.epoch_account.epoch_nr,
+            relayer: None,
+        });
+    }
+
+    Ok(())
+}
+
+/// Verifies a single entry, marks it claimed and adds it to the payouts, returning the
+/// position of its payout.
+fn claim_entry<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ClaimMany<'info>>,
+    entry: &ClaimEntry,
+    accounts: &'info [AccountInfo<'info>],
+    payouts: &mut Vec<VaultPayout<'info>>,
+    claimed_at: i64,
+) -> Result<usize> {
+    let rewards_account_key = ctx.accounts.rewards_account.key();
+    let receiver = ctx.accounts.receiver.key();
+    let (epoch_info, from_info, claim_tracking_info) = (&accounts[0], &accounts[1], &accounts[2]);
+
+    let position = match payouts
+        .iter()
+        .position(|payout| payout.epoch_account.key() == epoch_info.key())
+    {
+        Some(position) => position,
+        None => {
+            let epoch_account = Account::<EpochAccount>::try_from(epoch_info)?;
+            let expected_epoch = Pubkey::create_program_address(
+                &[
+                    b"EpochAccount".as_ref(),
+                    rewards_account_key.as_ref(),
+                    &entry.epoch_nr.to_le_bytes(),
+                    &[epoch_account.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidEpochNr)?;
+            require_keys_eq!(expected_epoch, epoch_info.key(), ErrorCode::InvalidEpochNr);
+
+            payouts.push(VaultPayout {
+                epoch_account,
+                from: from_info.clone(),
+                amount: 0,
+                unshared_amount: 0,
+                unshared_net_amount: 0,
+            });
+            payouts.len() - 1
+        }
+    };
+    let payout = &mut payouts[position];
+    let epoch_account = &mut payout.epoch_account;
+
+    epoch_account.assert_claimable(claimed_at)?;
+    require_keys_eq!(
+        epoch_account.mint_at(entry.mint_index)?,
+        ctx.accounts.mint_account.key(),
+        ErrorCode::InvalidMintAccount
+    );
+    require!(
+        !epoch_account.is_minted(entry.mint_index),
+        ErrorCode::MintModeEpoch
+    );
+
+    // The vault must be the epoch's token account.
+    require_keys_eq!(payout.from.key(), from_info.key(), ErrorCode::OwnerMismatch);
+    let from = InterfaceAccount::<TokenAccount>::try_from(from_info)?;
+    require_keys_eq!(from.owner, epoch_info.key(), ErrorCode::OwnerMismatch);
+    require_keys_neq!(from_info.key(), ctx.accounts.to.key());
+
+    // Verify the merkle proof.
+    let node = merkle_proof::claim_leaf(entry.index, &receiver, entry.amount, entry.mint_index);
+    require!(
+        merkle_proof::verify(entry.proof.clone(), epoch_account.hash, node),
+        ErrorCode::InvalidProof
+    );
+
+    // Mark it claimed, which fails if the drop was already claimed.
+    let claim = ClaimStatus {
+        is_claimed: true,
+        receiver,
+        claimed_at,
+        amount: entry.amount,
+        payer: receiver,
+    };
+    if epoch_account.claim_bitmap_shards > 0 {
+        let claim_bitmap = AccountLoader::<ClaimBitmap>::try_from(claim_tracking_info)?;
+        track_claim(epoch_account, None, Some(&claim_bitmap), entry.index, claim)?;
+    } else {
+        create_claim_status(ctx, entry, epoch_info, claim_tracking_info, claim)?;
+    }
+
+    // Update the distributor's total amount claimed and number of nodes claimed.
+    epoch_account.record_claim(entry.mint_index, entry.amount)?;
+    payout.amount += entry.amount;
+
+    Ok(position)
+}
+
+/// Creates the [ClaimStatus] of an entry, which fails if the drop was already claimed.
+fn create_claim_status<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ClaimMany<'info>>,
+    entry: &ClaimEntry,
+    epoch_info: &AccountInfo<'info>,
+    claim_tracking_info: &AccountInfo<'info>,
+    claim: ClaimStatus,
+) -> Result<()> {
+    let rewards_account_key = ctx.accounts.rewards_account.key();
+
+    let index_bytes = entry.index.to_le_bytes();
+    let epoch_key_bytes = epoch_info.key().to_bytes();
+    let (expected_claim_status, claim_status_bump) = Pubkey::find_program_address(
+        &[
+            b"ClaimStatus".as_ref(),
+            rewards_account_key.as_ref(),
+            &index_bytes,
+            &epoch_key_bytes,
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        expected_claim_status,
+        claim_tracking_info.key(),
+        ErrorCode::InvalidRemainingAccounts
+    );
+    require!(
+        claim_tracking_info.data_is_empty(),
+        ErrorCode::DropAlreadyClaimed
+    );
+
+    account::create_pda_account(
+        &ctx.accounts.receiver.to_account_info(),
+        claim_tracking_info,
+        &ctx.accounts.system_program.to_account_info(),
+        8 + ClaimStatus::LEN,
+        &[
+            b"ClaimStatus".as_ref(),
+            rewards_account_key.as_ref(),
+            &index_bytes,
+            &epoch_key_bytes,
+            &[claim_status_bump],
+        ],
+    )?;
+
+    claim.try_serialize(&mut &mut claim_tracking_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
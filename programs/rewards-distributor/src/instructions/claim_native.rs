@@ -0,0 +1,158 @@
+use crate::errors::ErrorCode;
+use crate::events::ClaimedEvent;
+use crate::instructions::track_claim;
+use crate::state::{
+    ClaimBitmap, ClaimStatus, DistributionMode, EpochAccount, RewardsAccount, PAUSE_CLAIMS,
+};
+use crate::utils::merkle_proof;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+/// [rewards_distributor::claim_native] accounts.
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct ClaimNative<'info> {
+    /// The [RewardsAccount].
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount].
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_account.epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump = epoch_account.bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// Status of the claim, for epochs without a [ClaimBitmap].
+    #[account(
+        init,
+        seeds = [
+            b"ClaimStatus".as_ref(),
+            rewards_account.key().as_ref(),
+            index.to_le_bytes().as_ref(),
+            epoch_account.key().to_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + ClaimStatus::LEN,
+        payer = receiver
+    )]
+    pub claim_status: Option<Account<'info, ClaimStatus>>,
+
+    /// The [ClaimBitmap] shard tracking `index`, for epochs with a bitmap.
+    #[account(mut)]
+    pub claim_bitmap: Option<AccountLoader<'info, ClaimBitmap>>,
+
+    /// Lamport vault of the epoch.
+    #[account(
+        mut,
+        seeds = [
+             b"EpochVault".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_account.epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Who is claiming the lamports.
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// claim native handler.
+pub fn claim_native_handler(
+    ctx: Context<ClaimNative>,
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_CLAIMS),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(
+        rewards_account.distribution_mode == DistributionMode::PerEpoch,
+        ErrorCode::InvalidDistributionMode
+    );
+
+    let clock = Clock::get()?;
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    epoch_account.assert_claimable(clock.unix_timestamp)?;
+    require!(epoch_account.is_native, ErrorCode::NotNativeEpoch);
+
+    let receiver = ctx.accounts.receiver.key();
+
+    // Verify the merkle proof, leaves use the same format as token epochs.
+    let node = merkle_proof::claim_leaf(index, &receiver, amount, 0);
+    require!(
+        merkle_proof::verify(proof, epoch_account.hash, node),
+        ErrorCode::InvalidProof
+    );
+
+    // Mark it claimed and send the lamports.
+    track_claim(
+        epoch_account,
+        ctx.accounts.claim_status.as_mut(),
+        ctx.accounts.claim_bitmap.as_ref(),
+        index,
+        ClaimStatus {
+            is_claimed: true,
+            receiver,
+            claimed_at: clock.unix_timestamp,
+            amount,
+            payer: receiver,
+        },
+    )?;
+
+    // Update the distributor's total amount claimed and number of nodes claimed.
+    epoch_account.record_claim(0, amount)?;
+
+    // The vault must stay rent-exempt.
+    let available = ctx
+        .accounts
+        .vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(amount <= available, ErrorCode::InsufficientVaultBalance);
+
+    // Define the seeds for signing the transaction.
+    let rewards_account_key = rewards_account.key();
+    let epoch_nr_bytes = epoch_account.epoch_nr.to_le_bytes();
+    let seeds = [
+        b"EpochVault".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_nr_bytes,
+        &[ctx.bumps.vault],
+    ];
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.receiver.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+    )?;
+
+    // Emit an event indicating that the claim has been made, the amount is in lamports.
+    emit!(ClaimedEvent {
+        index,
+        receiver,
+        amount,
+        net_amount: amount,
+        epoch_nr: epoch_account.epoch_nr,
+        relayer: None,
+    });
+
+    Ok(())
+}
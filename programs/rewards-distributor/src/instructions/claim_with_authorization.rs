@@ -0,0 +1,233 @@
+use crate::errors::ErrorCode;
+use crate::events::ClaimedEvent;
+use crate::instructions::track_claim;
+use crate::state::{
+    ClaimBitmap, ClaimStatus, DistributionMode, EpochAccount, RewardsAccount, PAUSE_CLAIMS,
+};
+use crate::utils::{ed25519, merkle_proof, token};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+/// [rewards_distributor::claim_with_authorization] accounts.
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct ClaimWithAuthorization<'info> {
+    /// The [RewardsAccount].
+    #[account(mut)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount].
+    #[account(mut,
+        address = from.owner
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// Status of the claim, for epochs without a [ClaimBitmap].
+    #[account(
+        init,
+        seeds = [
+            b"ClaimStatus".as_ref(),
+            rewards_account.key().as_ref(),
+            index.to_le_bytes().as_ref(),
+            epoch_account.key().to_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + ClaimStatus::LEN,
+        payer = relayer
+    )]
+    pub claim_status: Option<Account<'info, ClaimStatus>>,
+
+    /// The [ClaimBitmap] shard tracking `index`, for epochs with a bitmap.
+    #[account(mut)]
+    pub claim_bitmap: Option<AccountLoader<'info, ClaimBitmap>>,
+
+    /// Distributor ATA containing the tokens to distribute.
+    #[account(mut)]
+    pub from: InterfaceAccount<'info, TokenAccount>,
+
+    // Account to send the claimed tokens to.
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = mint_account,
+        associated_token::authority = receiver,
+        associated_token::token_program = token_program
+    )]
+    pub to: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Who the tokens are claimed for, proven by the ed25519 authorization.
+    pub receiver: UncheckedAccount<'info>,
+
+    /// Who pays the transaction fees and the rent.
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(mut)]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: The instructions sysvar, used to find the ed25519 verification.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated [Token] program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Builds the message the receiver signs to authorize a relayed claim.
+pub fn claim_authorization_message(
+    rewards_account: &Pubkey,
+    epoch_nr: u64,
+    index: u64,
+    amount: u64,
+    destination: &Pubkey,
+    expiry: i64,
+) -> Vec<u8> {
+    [
+        crate::ID.as_ref(),
+        rewards_account.as_ref(),
+        &epoch_nr.to_le_bytes(),
+        &index.to_le_bytes(),
+        &amount.to_le_bytes(),
+        destination.as_ref(),
+        &expiry.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// claim with authorization handler.
+pub fn claim_with_authorization_handler(
+    ctx: Context<ClaimWithAuthorization>,
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+    expiry: i64,
+    mint_index: u8,
+) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_CLAIMS),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(
+        rewards_account.distribution_mode == DistributionMode::PerEpoch,
+        ErrorCode::InvalidDistributionMode
+    );
+
+    let clock = Clock::get()?;
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    epoch_account.assert_claimable(clock.unix_timestamp)?;
+    require_keys_eq!(
+        epoch_account.mint_at(mint_index)?,
+        ctx.accounts.mint_account.key(),
+        ErrorCode::InvalidMintAccount
+    );
+    require!(
+        !epoch_account.is_minted(mint_index),
+        ErrorCode::MintModeEpoch
+    );
+
+    require!(
+        clock.unix_timestamp <= expiry,
+        ErrorCode::AuthorizationExpired
+    );
+
+    // Ensure the `from` and `to` accounts are different.
+    require_keys_neq!(ctx.accounts.from.key(), ctx.accounts.to.key());
+
+    let receiver = ctx.accounts.receiver.key();
+
+    // The instruction right before this one must be the ed25519 verification
+    // of the receiver's signature over the claim parameters.
+    let instructions_sysvar = ctx.accounts.instructions_sysvar.to_account_info();
+    let current_index = load_current_index_checked(&instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::InvalidAuthorization);
+    let ed25519_ix = load_instruction_at_checked(current_index as usize - 1, &instructions_sysvar)?;
+    let message = claim_authorization_message(
+        &rewards_account.key(),
+        epoch_account.epoch_nr,
+        index,
+        amount,
+        &ctx.accounts.to.key(),
+        expiry,
+    );
+    require!(
+        ed25519::verify(&ed25519_ix, &receiver, &message),
+        ErrorCode::InvalidAuthorization
+    );
+
+    // Verify the merkle proof.
+    let node = merkle_proof::claim_leaf(index, &receiver, amount, mint_index);
+    require!(
+        merkle_proof::verify(proof, epoch_account.hash, node),
+        ErrorCode::InvalidProof
+    );
+
+    // Mark it claimed and send the tokens.
+    track_claim(
+        epoch_account,
+        ctx.accounts.claim_status.as_mut(),
+        ctx.accounts.claim_bitmap.as_ref(),
+        index,
+        ClaimStatus {
+            is_claimed: true,
+            receiver,
+            claimed_at: clock.unix_timestamp,
+            amount,
+            payer: ctx.accounts.relayer.key(),
+        },
+    )?;
+
+    // Update the distributor's total amount claimed and number of nodes claimed.
+    epoch_account.record_claim(mint_index, amount)?;
+
+    // Define the seeds for signing the transaction.
+    let rewards_account_key = rewards_account.key();
+    let seeds = [
+        b"EpochAccount".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_account.epoch_nr.to_le_bytes(),
+        &[epoch_account.bump],
+    ];
+
+    // The receiver gets the amount minus the transfer fee withheld by the mint, if any.
+    let net_amount =
+        amount - token::transfer_fee(&ctx.accounts.mint_account.to_account_info(), amount)?;
+
+    // Invoke the transfer instruction on the token program
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.from.to_account_info(),
+                mint: ctx.accounts.mint_account.to_account_info(),
+                to: ctx.accounts.to.to_account_info(),
+                authority: epoch_account.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+        ctx.accounts.mint_account.decimals,
+    )?;
+
+    // Emit an event indicating that the claim has been made.
+    emit!(ClaimedEvent {
+        index,
+        receiver,
+        amount,
+        net_amount,
+        epoch_nr: epoch_account.epoch_nr,
+        relayer: Some(ctx.accounts.relayer.key()),
+    });
+
+    Ok(())
+}
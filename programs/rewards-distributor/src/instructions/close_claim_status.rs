@@ -0,0 +1,59 @@
+use crate::errors::ErrorCode;
+use crate::events::ClaimStatusClosed;
+use crate::state::{ClaimStatus, EpochAccount, RewardsAccount, PAUSE_CLAIMS};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::close_claim_status] accounts.
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct CloseClaimStatus<'info> {
+    /// The [RewardsAccount].
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// CHECK: The [EpochAccount] of the claim, bound by the [ClaimStatus] seeds.
+    /// It may already be closed.
+    pub epoch_account: UncheckedAccount<'info>,
+
+    /// The [ClaimStatus] to close.
+    #[account(
+        mut,
+        seeds = [
+            b"ClaimStatus".as_ref(),
+            rewards_account.key().as_ref(),
+            index.to_le_bytes().as_ref(),
+            epoch_account.key().to_bytes().as_ref()
+        ],
+        bump,
+        close = payer
+    )]
+    pub claim_status: Account<'info, ClaimStatus>,
+
+    /// CHECK: Who paid the rent of the [ClaimStatus], receiving it back.
+    #[account(mut, address = claim_status.payer @ ErrorCode::InvalidRentPayer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+/// close claim status handler.
+pub fn close_claim_status_handler(ctx: Context<CloseClaimStatus>, index: u64) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_CLAIMS),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    // Without its ClaimStatus a leaf could be claimed again, so the epoch must
+    // either be closed or swept, both of which reject any further claim.
+    let epoch_info = ctx.accounts.epoch_account.to_account_info();
+    if epoch_info.owner == &crate::ID && !epoch_info.data_is_empty() {
+        let epoch_account = EpochAccount::try_deserialize(&mut &epoch_info.try_borrow_data()?[..])?;
+        require!(epoch_account.is_swept, ErrorCode::EpochStillClaimable);
+    }
+
+    emit!(ClaimStatusClosed {
+        epoch_account: epoch_info.key(),
+        index,
+        payer: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
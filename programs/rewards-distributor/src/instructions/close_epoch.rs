@@ -0,0 +1,262 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochClosed;
+use crate::state::{
+    ClaimBitmap, DistributionMode, EpochAccount, ManagerAction, ManagerProposal, RewardsAccount,
+    Role, RoleGrant, PAUSE_APPROVALS,
+};
+use crate::utils::token;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{
+    close_account, harvest_withheld_tokens_to_mint, CloseAccount, HarvestWithheldTokensToMint,
+    Mint, TokenAccount, TokenInterface,
+};
+
+/// Number of `remaining_accounts` expected for each additional mint of the epoch:
+/// the mint, its epoch ATA and the token program of the mint.
+pub const ACCOUNTS_PER_BONUS_MINT: usize = 3;
+
+/// Number of `remaining_accounts` expected for each [ClaimBitmap] shard of the epoch:
+/// the shard and who paid its rent.
+pub const ACCOUNTS_PER_CLAIM_BITMAP: usize = 2;
+
+/// [rewards_distributor::close_epoch] accounts.
+///
+/// For every additional mint of the epoch, `remaining_accounts` must contain, in
+/// order, the mint (writable), its epoch ATA (writable) and its token program.
+/// They are followed, for every [ClaimBitmap] shard of the epoch in shard order, by
+/// the shard (writable) and its rent payer (writable).
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct CloseEpoch<'info> {
+    /// The [RewardsAccount]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump,
+        close = creator
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] of the action, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    /// Epoch ATA
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub epoch_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Agent who paid the rent of the [EpochAccount], receiving it back.
+    #[account(mut, address = epoch_account.creator @ ErrorCode::InvalidRentPayer)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: Manager who paid the rent of the epoch ATA, receiving it back.
+    #[account(mut, address = epoch_account.approver @ ErrorCode::InvalidRentPayer)]
+    pub approver: UncheckedAccount<'info>,
+
+    #[account(mut, address = epoch_account.mint @ ErrorCode::InvalidMintAccount)]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn close_epoch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseEpoch<'info>>,
+    epoch_nr: u64,
+) -> Result<()> {
+    ctx.accounts.rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        ctx.accounts.role_grant.as_deref(),
+        Role::ConfigAdmin,
+        &ManagerAction::CloseEpoch { epoch_nr },
+    )?;
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    // In cumulative mode the latest approved vault is carried over to the next epoch.
+    require!(
+        rewards_account.distribution_mode == DistributionMode::PerEpoch
+            || epoch_nr < rewards_account.current_approved_epoch,
+        ErrorCode::InvalidEpochNr
+    );
+
+    // The epoch is done once everything was claimed or swept. An empty vault alone does not
+    // tell, in mint mode claims never go through it. In cumulative mode the unclaimed
+    // rewards were carried over to the next epoch.
+    let epoch_account = &ctx.accounts.epoch_account;
+    require!(epoch_account.is_approved, ErrorCode::EpochShouldBeApproved);
+    require!(
+        rewards_account.distribution_mode == DistributionMode::Cumulative
+            || epoch_account.is_settled(),
+        ErrorCode::EpochNotSettled
+    );
+    require!(
+        ctx.accounts.epoch_token_account.amount == 0,
+        ErrorCode::EpochVaultNotEmpty
+    );
+
+    // Define the seeds for signing the transaction.
+    let rewards_account_key = rewards_account.key();
+    let epoch_nr_bytes = epoch_account.epoch_nr.to_le_bytes();
+    let seeds = [
+        b"EpochAccount".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_nr_bytes,
+        &[epoch_account.bump],
+    ];
+
+    // The ATAs of the additional mints must be empty and are closed too.
+    let bonus_mint_accounts_len = epoch_account.bonus_mints.len() * ACCOUNTS_PER_BONUS_MINT;
+    require!(
+        ctx.remaining_accounts.len()
+            == bonus_mint_accounts_len
+                + epoch_account.claim_bitmap_shards as usize * ACCOUNTS_PER_CLAIM_BITMAP,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    let (bonus_mint_accounts, claim_bitmap_accounts) =
+        ctx.remaining_accounts.split_at(bonus_mint_accounts_len);
+    for (bonus_mint, accounts) in epoch_account
+        .bonus_mints
+        .iter()
+        .zip(bonus_mint_accounts.chunks(ACCOUNTS_PER_BONUS_MINT))
+    {
+        let (mint, vault, token_program) = (&accounts[0], &accounts[1], &accounts[2]);
+        require_keys_eq!(mint.key(), bonus_mint.mint, ErrorCode::InvalidMintAccount);
+        require_keys_eq!(
+            token_program.key(),
+            *mint.owner,
+            ErrorCode::InvalidRemainingAccounts
+        );
+        require_keys_eq!(
+            vault.key(),
+            get_associated_token_address_with_program_id(
+                &epoch_account.key(),
+                mint.key,
+                token_program.key
+            ),
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        // Never funded, nothing to close.
+        if vault.data_is_empty() {
+            continue;
+        }
+        let vault_account = InterfaceAccount::<TokenAccount>::try_from(vault)?;
+        require!(vault_account.amount == 0, ErrorCode::EpochVaultNotEmpty);
+
+        close_vault(
+            token_program,
+            mint,
+            vault,
+            &ctx.accounts.approver.to_account_info(),
+            &epoch_account.to_account_info(),
+            &seeds,
+        )?;
+    }
+
+    // The claim bitmap shards are closed too, each to who paid its rent.
+    close_claim_bitmaps(&epoch_account.key(), claim_bitmap_accounts)?;
+
+    // Close the vault, the epoch account itself is closed on exit. Without it
+    // no claim can be made on the epoch anymore.
+    close_vault(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint_account.to_account_info(),
+        &ctx.accounts.epoch_token_account.to_account_info(),
+        &ctx.accounts.approver.to_account_info(),
+        &epoch_account.to_account_info(),
+        &seeds,
+    )?;
+
+    emit!(EpochClosed { epoch_nr });
+
+    Ok(())
+}
+
+/// Closes the empty epoch ATA `vault`, sending its rent to `destination`.
+fn close_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    epoch_account: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    // Transfer fees withheld in the vault prevent closing it, move them to the mint.
+    if token::transfer_fee_config(mint)?.is_some() {
+        harvest_withheld_tokens_to_mint(
+            CpiContext::new(
+                token_program.clone(),
+                HarvestWithheldTokensToMint {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ),
+            vec![vault.clone()],
+        )?;
+    }
+
+    close_account(
+        CpiContext::new(
+            token_program.clone(),
+            CloseAccount {
+                account: vault.clone(),
+                destination: destination.clone(),
+                authority: epoch_account.clone(),
+            },
+        )
+        .with_signer(&[seeds]),
+    )
+}
+
+/// Closes the [ClaimBitmap] shards of `epoch_account`, passed in shard order each followed
+/// by who paid its rent, sending the rent back to them.
+pub(crate) fn close_claim_bitmaps<'info>(
+    epoch_account: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    for (shard, accounts) in (0..).zip(accounts.chunks(ACCOUNTS_PER_CLAIM_BITMAP)) {
+        let (claim_bitmap_info, payer) = (&accounts[0], &accounts[1]);
+        let claim_bitmap = AccountLoader::<ClaimBitmap>::try_from(claim_bitmap_info)?;
+        {
+            let claim_bitmap = claim_bitmap.load()?;
+            require_keys_eq!(
+                claim_bitmap.epoch,
+                *epoch_account,
+                ErrorCode::InvalidClaimTracking
+            );
+            require!(
+                claim_bitmap.shard == shard,
+                ErrorCode::InvalidClaimBitmapShard
+            );
+            require_keys_eq!(claim_bitmap.payer, payer.key(), ErrorCode::InvalidRentPayer);
+        }
+
+        claim_bitmap.close(payer.clone())?;
+    }
+
+    Ok(())
+}
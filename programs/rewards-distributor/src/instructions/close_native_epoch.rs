@@ -0,0 +1,129 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochClosed;
+use crate::instructions::{close_claim_bitmaps, ACCOUNTS_PER_CLAIM_BITMAP};
+use crate::state::{
+    EpochAccount, ManagerAction, ManagerProposal, RewardsAccount, Role, RoleGrant, PAUSE_APPROVALS,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+/// [rewards_distributor::close_native_epoch] accounts.
+///
+/// For every [ClaimBitmap](crate::state::ClaimBitmap) shard of the epoch in shard order,
+/// `remaining_accounts` must contain the shard (writable) and its rent payer (writable).
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct CloseNativeEpoch<'info> {
+    /// The [RewardsAccount]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump,
+        close = creator
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] of the action, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    /// Lamport vault of the epoch.
+    #[account(
+        mut,
+        seeds = [
+             b"EpochVault".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Agent who paid the rent of the [EpochAccount], receiving it back.
+    #[account(mut, address = epoch_account.creator @ ErrorCode::InvalidRentPayer)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: Manager who paid the rent-exempt minimum of the vault, receiving it back.
+    #[account(mut, address = epoch_account.approver @ ErrorCode::InvalidRentPayer)]
+    pub approver: UncheckedAccount<'info>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn close_native_epoch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseNativeEpoch<'info>>,
+    epoch_nr: u64,
+) -> Result<()> {
+    ctx.accounts.rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        ctx.accounts.role_grant.as_deref(),
+        Role::ConfigAdmin,
+        &ManagerAction::CloseEpoch { epoch_nr },
+    )?;
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    // The epoch is done once everything was claimed or swept, down to the rent-exempt minimum.
+    let epoch_account = &ctx.accounts.epoch_account;
+    require!(epoch_account.is_native, ErrorCode::NotNativeEpoch);
+    require!(epoch_account.is_approved, ErrorCode::EpochShouldBeApproved);
+    require!(epoch_account.is_settled(), ErrorCode::EpochNotSettled);
+    let rent = Rent::get()?.minimum_balance(0);
+    require!(
+        ctx.accounts.vault.lamports() <= rent,
+        ErrorCode::EpochVaultNotEmpty
+    );
+
+    // The claim bitmap shards are closed too, each to who paid its rent.
+    require!(
+        ctx.remaining_accounts.len()
+            == epoch_account.claim_bitmap_shards as usize * ACCOUNTS_PER_CLAIM_BITMAP,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    close_claim_bitmaps(&epoch_account.key(), ctx.remaining_accounts)?;
+
+    // Empty the vault, the epoch account itself is closed on exit. Without it
+    // no claim can be made on the epoch anymore.
+    let rewards_account_key = rewards_account.key();
+    let epoch_nr_bytes = epoch_nr.to_le_bytes();
+    let seeds = [
+        b"EpochVault".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_nr_bytes,
+        &[ctx.bumps.vault],
+    ];
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.approver.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        ctx.accounts.vault.lamports(),
+    )?;
+
+    emit!(EpochClosed { epoch_nr });
+
+    Ok(())
+}
@@ -1,17 +1,24 @@
 use crate::{
     errors::ErrorCode,
     events::EpochCorrected,
-    state::{EpochAccount, RewardsAccount},
+    state::{
+        DistributionMode, EpochAccount, RewardsAccount, Role, RoleGrant, RootSubmission,
+        MAX_METADATA_URI_LEN, PAUSE_SUBMISSIONS,
+    },
+    utils::token,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 
 /// [rewards_distributor::correct_epoch] accounts.
 #[derive(Accounts)]
-#[instruction( epoch_nr: u64)]
+#[instruction(epoch_nr: u64, root: [u8; 32], max_total_claim: u64, max_num_nodes: u64, metadata_uri: String)]
 pub struct CorrectEpoch<'info> {
     /// The [RewardsAccount]
-    #[account(mut, has_one = agent @ ErrorCode::Unauthorized)]
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&agent.key(), Role::Submitter, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
     pub rewards_account: Account<'info, RewardsAccount>,
 
     /// The [EpochAccount]
@@ -21,39 +28,136 @@ pub struct CorrectEpoch<'info> {
          rewards_account.key().as_ref(),
          epoch_nr.to_le_bytes().as_ref()
     ],
-    bump
+    bump,
+    realloc = epoch_account.space_after_correction(&rewards_account, &agent.key(), &metadata_uri),
+    realloc::payer = agent,
+    realloc::zero = false,
     )]
     pub epoch_account: Account<'info, EpochAccount>,
 
-    /// The mint to distribute.
-    pub mint: Account<'info, Mint>,
+    /// The mint to distribute, `None` for an epoch distributing native SOL.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
 
     /// current manager of the program.
+    #[account(mut)]
     pub agent: Signer<'info>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
 }
 
 pub fn correct_epoch_handler(
     ctx: Context<CorrectEpoch>,
     epoch_nr: u64,
     root: [u8; 32],
+    max_total_claim: u64,
+    max_num_nodes: u64,
+    metadata_uri: String,
+    distribution_hash: [u8; 32],
 ) -> Result<()> {
     let rewards_account = &mut ctx.accounts.rewards_account;
     let epoch_account = &mut ctx.accounts.epoch_account;
 
-    require!(!rewards_account.is_paused, ErrorCode::ShouldNotBePaused);
+    require!(
+        !rewards_account.is_paused(PAUSE_SUBMISSIONS),
+        ErrorCode::ShouldNotBePaused
+    );
 
     require!(
         !epoch_account.is_approved,
         ErrorCode::EpochShouldNotBeApproved
     );
 
+    require!(
+        max_total_claim > 0 && max_num_nodes > 0,
+        ErrorCode::InvalidEpochTotals
+    );
+
+    require!(
+        metadata_uri.len() <= MAX_METADATA_URI_LEN,
+        ErrorCode::MetadataUriTooLong
+    );
+
+    if let Some(mint) = &ctx.accounts.mint {
+        token::assert_supported_mint(&mint.to_account_info())?;
+    }
+
+    let mint = ctx
+        .accounts
+        .mint
+        .as_ref()
+        .map(|mint| mint.key())
+        .unwrap_or_default();
+
+    // A single quorum agent only records what it computed, which diverges from the epoch
+    // unless it matches what the others submitted.
+    if !epoch_account.applies_correction(rewards_account) {
+        epoch_account.submit_root(
+            rewards_account,
+            RootSubmission {
+                agent: ctx.accounts.agent.key(),
+                root,
+                max_total_claim,
+                max_num_nodes,
+                mint,
+            },
+        );
+        return Ok(());
+    }
+
+    // The rewards rolled over into the epoch are held in the ATA of its mint.
+    require!(
+        epoch_account.rolled_over_amount == 0
+            || ctx.accounts.mint.as_ref().map(|mint| mint.key()) == Some(epoch_account.mint),
+        ErrorCode::InvalidMintAccount
+    );
+
+    // Native SOL epochs cannot carry their balance over in cumulative mode.
+    require!(
+        ctx.accounts.mint.is_some()
+            || rewards_account.distribution_mode == DistributionMode::PerEpoch,
+        ErrorCode::InvalidDistributionMode
+    );
+
     epoch_account.hash = root;
-    epoch_account.mint = ctx.accounts.mint.key();
+    epoch_account.mint = mint;
+    epoch_account.is_native = ctx.accounts.mint.is_none();
+    epoch_account.max_total_claim = max_total_claim;
+    epoch_account.max_num_nodes = max_num_nodes;
+    epoch_account.correction_count += 1;
+    epoch_account.is_rejected = false;
+    epoch_account.distribution_hash = distribution_hash;
+    epoch_account.metadata_uri = metadata_uri.clone();
+
+    // Submissions were made for the previous root, in quorum mode the correction
+    // submits the new one.
+    epoch_account.root_submissions.clear();
+    if rewards_account.agent_quorum > 0 {
+        epoch_account.submit_root(
+            rewards_account,
+            RootSubmission {
+                agent: ctx.accounts.agent.key(),
+                root,
+                max_total_claim,
+                max_num_nodes,
+                mint,
+            },
+        );
+    }
 
     emit!(EpochCorrected {
         root,
         epoch_nr,
-        mint: ctx.accounts.mint.key(),
+        mint: epoch_account.mint,
+        max_total_claim,
+        max_num_nodes,
+        correction_count: epoch_account.correction_count,
+        metadata_uri,
+        distribution_hash,
     });
 
     Ok(())
@@ -0,0 +1,71 @@
+use crate::errors::ErrorCode;
+use crate::events::ProposalCreated;
+use crate::state::{
+    assert_valid_agent_quorum, assert_valid_multisig, ManagerAction, ManagerProposal,
+    RewardsAccount,
+};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::create_proposal] accounts.
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    /// The [RewardsAccount].
+    #[account(mut)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [ManagerProposal] to create.
+    #[account(
+        init,
+        seeds = [
+            b"ManagerProposal".as_ref(),
+            rewards_account.key().as_ref(),
+            (rewards_account.proposal_count + 1).to_le_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + ManagerProposal::INIT_SPACE,
+        payer = member
+    )]
+    pub proposal: Account<'info, ManagerProposal>,
+
+    /// A member of the manager multisig.
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// create proposal handler.
+pub fn create_proposal_handler(ctx: Context<CreateProposal>, action: ManagerAction) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    match &action {
+        ManagerAction::SetMultisig { members, threshold } => {
+            assert_valid_multisig(members, *threshold)?
+        }
+        ManagerAction::SetAgentQuorum { agents, quorum } => {
+            assert_valid_agent_quorum(agents, *quorum)?
+        }
+        ManagerAction::ProposeAgent { overlap, .. } => {
+            require!(*overlap >= 0, ErrorCode::InvalidAgentOverlap)
+        }
+        _ => {}
+    }
+
+    rewards_account.proposal_count += 1;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.rewards_account = rewards_account.key();
+    proposal.proposal_nr = rewards_account.proposal_count;
+    proposal.proposer = ctx.accounts.member.key();
+    proposal.action = action.clone();
+
+    emit!(ProposalCreated {
+        proposal_nr: proposal.proposal_nr,
+        proposer: proposal.proposer,
+        action,
+    });
+
+    // Creating a proposal approves it, which executes it with a threshold of 1.
+    proposal.approve(rewards_account, &ctx.accounts.member.key())?;
+    rewards_account.execute_proposal(proposal)
+}
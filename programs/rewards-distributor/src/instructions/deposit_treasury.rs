@@ -0,0 +1,82 @@
+use crate::errors::ErrorCode;
+use crate::events::TreasuryDeposited;
+use crate::state::{RewardsAccount, PAUSE_ADMIN};
+use crate::utils::token;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// [rewards_distributor::deposit_treasury] accounts.
+#[derive(Accounts)]
+pub struct DepositTreasury<'info> {
+    /// The [RewardsAccount]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// Treasury vault of the mint, the token account funding the epochs at approval.
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        seeds = [
+             b"TreasuryVault".as_ref(),
+             rewards_account.key().as_ref(),
+             mint_account.key().as_ref()
+        ],
+        bump,
+        token::mint = mint_account,
+        token::authority = treasury_vault,
+        token::token_program = token_program,
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Anyone depositing tokens in the treasury vault.
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    /// Depositor token account
+    #[account(mut)]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// deposit treasury handler.
+pub fn deposit_treasury_handler(ctx: Context<DepositTreasury>, amount: u64) -> Result<()> {
+    require!(
+        !ctx.accounts.rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    // The depositor also pays the transfer fee, if any, so that the vault receives `amount`.
+    let mint_info = ctx.accounts.mint_account.to_account_info();
+    let gross_amount = token::gross_amount(&mint_info, amount)?;
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.depositor_token_account.to_account_info(),
+                mint: mint_info,
+                to: ctx.accounts.treasury_vault.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+            },
+        ),
+        gross_amount,
+        ctx.accounts.mint_account.decimals,
+    )?;
+
+    emit!(TreasuryDeposited {
+        mint: ctx.accounts.mint_account.key(),
+        depositor: ctx.accounts.depositor.key(),
+        amount: gross_amount,
+        net_amount: amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::errors::ErrorCode;
+use crate::events::TimelockOperationExecuted;
+use crate::state::{RewardsAccount, TimelockOperation};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::execute_timelock_operation] accounts.
+#[derive(Accounts)]
+#[instruction(operation_nr: u64)]
+pub struct ExecuteTimelockOperation<'info> {
+    /// The [RewardsAccount].
+    #[account(mut)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [TimelockOperation] to execute.
+    #[account(
+        mut,
+        seeds = [
+            b"TimelockOperation".as_ref(),
+            rewards_account.key().as_ref(),
+            operation_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub operation: Account<'info, TimelockOperation>,
+}
+
+/// execute timelock operation handler.
+pub fn execute_timelock_operation_handler(
+    ctx: Context<ExecuteTimelockOperation>,
+    operation_nr: u64,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    let operation = &mut ctx.accounts.operation;
+
+    require!(
+        operation.is_pending(),
+        ErrorCode::TimelockOperationNotPending
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= operation.eta,
+        ErrorCode::TimelockNotReady
+    );
+
+    rewards_account.execute_timelock_action(&operation.action)?;
+    operation.is_executed = true;
+
+    emit!(TimelockOperationExecuted { operation_nr });
+
+    Ok(())
+}
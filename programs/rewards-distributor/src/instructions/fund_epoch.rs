@@ -0,0 +1,145 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochFunded;
+use crate::state::{
+    EpochAccount, EpochTopUp, RewardsAccount, Role, RoleGrant, MAX_EPOCH_TOP_UPS, PAUSE_APPROVALS,
+};
+use crate::utils::token;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// [rewards_distributor::fund_epoch] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct FundEpoch<'info> {
+    /// The [RewardsAccount]
+    #[account(
+        constraint = rewards_account.has_role(&funder.key(), Role::Funder, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump,
+        realloc = epoch_account.space_after_top_up(&funder.key()),
+        realloc::payer = funder,
+        realloc::zero = false,
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// The manager or the funder of the program.
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// Epoch ATA
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub epoch_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Funder token account
+    #[account(mut)]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = epoch_account.mint @ ErrorCode::InvalidMintAccount)]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+/// fund epoch handler.
+pub fn fund_epoch_handler(ctx: Context<FundEpoch>, epoch_nr: u64, amount: u64) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    let funder = ctx.accounts.funder.key();
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(epoch_account.is_approved, ErrorCode::EpochShouldBeApproved);
+    require!(!epoch_account.is_swept, ErrorCode::EpochSwept);
+    // Claims of a mint mode epoch are minted, never paid from the vault.
+    require!(!epoch_account.is_mint_mode, ErrorCode::MintModeEpoch);
+
+    // Top-ups only cover what the vault lacks to pay the declared total, anything above
+    // could never be claimed.
+    let shortfall = epoch_account.max_total_claim.saturating_sub(
+        ctx.accounts
+            .epoch_token_account
+            .amount
+            .saturating_add(epoch_account.total_amount_claimed),
+    );
+    require!(amount <= shortfall, ErrorCode::EpochShortfallExceeded);
+
+    // The funder also pays the transfer fee, if any, so that the vault receives `amount`.
+    let mint_info = ctx.accounts.mint_account.to_account_info();
+    let gross_amount = token::gross_amount(&mint_info, amount)?;
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.funder_token_account.to_account_info(),
+                mint: mint_info,
+                to: ctx.accounts.epoch_token_account.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            },
+        ),
+        gross_amount,
+        ctx.accounts.mint_account.decimals,
+    )?;
+
+    // Top-ups are issued by the epoch too, and capped by the emission schedule.
+    epoch_account.funded_amount += amount;
+    epoch_account.issued_amount += amount;
+    rewards_account.assert_within_emission_schedule(
+        epoch_nr,
+        &epoch_account.mint,
+        epoch_account.issued_amount,
+    )?;
+
+    // Each funder is recorded, so that a veto refunds what they sent.
+    match epoch_account
+        .top_ups
+        .iter_mut()
+        .find(|top_up| top_up.funder == funder)
+    {
+        Some(top_up) => top_up.amount += amount,
+        None => {
+            require!(
+                epoch_account.top_ups.len() < MAX_EPOCH_TOP_UPS,
+                ErrorCode::TooManyEpochTopUps
+            );
+            epoch_account.top_ups.push(EpochTopUp { funder, amount });
+        }
+    }
+
+    emit!(EpochFunded {
+        epoch_nr,
+        funder,
+        mint: epoch_account.mint,
+        amount: gross_amount,
+        net_amount: amount,
+        funded_amount: epoch_account.funded_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochMintFunded;
+use crate::state::{EpochAccount, RewardsAccount, Role, RoleGrant, PAUSE_APPROVALS};
+use crate::utils::token;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// [rewards_distributor::fund_epoch_mint] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct FundEpochMint<'info> {
+    /// The [RewardsAccount]
+    #[account(
+        constraint = rewards_account.has_role(&manager.key(), Role::Funder, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// current manager of the program.
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    /// Epoch ATA of the mint
+    #[account(
+        init_if_needed,
+        payer = manager,
+        associated_token::mint = mint_account,
+        associated_token::authority = epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub epoch_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Manager token account
+    #[account(mut)]
+    pub manager_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated [Token] program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+/// fund epoch mint handler.
+pub fn fund_epoch_mint_handler(
+    ctx: Context<FundEpochMint>,
+    epoch_nr: u64,
+    mint_index: u8,
+    amount: u64,
+) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    // Added mints are funded once the manager approved the epoch.
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(epoch_account.is_approved, ErrorCode::EpochShouldBeApproved);
+    require!(!epoch_account.is_swept, ErrorCode::EpochSwept);
+
+    // The primary mint is funded when approving the epoch.
+    require!(mint_index > 0, ErrorCode::InvalidMintIndex);
+    require_keys_eq!(
+        epoch_account.mint_at(mint_index)?,
+        ctx.accounts.mint_account.key(),
+        ErrorCode::InvalidMintAccount
+    );
+    require!(
+        !epoch_account.bonus_mints[mint_index as usize - 1].is_swept,
+        ErrorCode::EpochSwept
+    );
+
+    // The manager also pays the transfer fee, if any, so that the vault receives `amount`.
+    let mint_info = ctx.accounts.mint_account.to_account_info();
+    let gross_amount = token::gross_amount(&mint_info, amount)?;
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.manager_token_account.to_account_info(),
+                mint: mint_info,
+                to: ctx.accounts.epoch_token_account.to_account_info(),
+                authority: ctx.accounts.manager.to_account_info(),
+            },
+        ),
+        gross_amount,
+        ctx.accounts.mint_account.decimals,
+    )?;
+
+    epoch_account.bonus_mints[mint_index as usize - 1].funded_amount += amount;
+
+    emit!(EpochMintFunded {
+        epoch_nr,
+        mint: ctx.accounts.mint_account.key(),
+        amount: gross_amount,
+        net_amount: amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::errors::ErrorCode;
+use crate::events::RoleGranted;
+use crate::state::{ManagerAction, ManagerProposal, RewardsAccount, Role, RoleGrant, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::grant_role] accounts.
+#[derive(Accounts)]
+#[instruction(role: Role, member: Pubkey)]
+pub struct GrantRole<'info> {
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] of the action, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// The [RewardsAccount].
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [RoleGrant] to create.
+    #[account(
+        init,
+        seeds = [
+            b"RoleGrant".as_ref(),
+            rewards_account.key().as_ref(),
+            [role as u8].as_ref(),
+            member.as_ref()
+        ],
+        bump,
+        space = 8 + RoleGrant::INIT_SPACE,
+        payer = manager
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// grant role handler.
+pub fn grant_role_handler(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+    ctx.accounts.rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        None,
+        Role::ConfigAdmin,
+        &ManagerAction::GrantRole { role, member },
+    )?;
+    require!(
+        !ctx.accounts.rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+    // Roles moving funds or changing the configuration are only handed out while the
+    // timelock is disabled, which itself takes a timelock operation.
+    require!(
+        role == Role::Pauser || !ctx.accounts.rewards_account.is_timelocked(),
+        ErrorCode::TimelockRequired
+    );
+
+    let role_grant = &mut ctx.accounts.role_grant;
+    role_grant.rewards_account = ctx.accounts.rewards_account.key();
+    role_grant.role = role;
+    role_grant.member = member;
+
+    emit!(RoleGranted { role, member });
+
+    Ok(())
+}
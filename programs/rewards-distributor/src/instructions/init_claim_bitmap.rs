@@ -0,0 +1,95 @@
+use crate::errors::ErrorCode;
+use crate::events::ClaimBitmapCreated;
+use crate::state::{ClaimBitmap, EpochAccount, RewardsAccount, Role, RoleGrant, PAUSE_SUBMISSIONS};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::init_claim_bitmap] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64, shard: u64)]
+pub struct InitClaimBitmap<'info> {
+    /// The [RewardsAccount].
+    #[account(
+        constraint = rewards_account.has_role(&authority.key(), Role::Submitter, role_grant.as_deref())
+            || rewards_account.has_role(&authority.key(), Role::ConfigAdmin, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount].
+    #[account(mut,
+        seeds = [
+            b"EpochAccount".as_ref(),
+            rewards_account.key().as_ref(),
+            epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// The [ClaimBitmap] shard to allocate.
+    #[account(
+        init,
+        seeds = [
+            b"ClaimBitmap".as_ref(),
+            epoch_account.key().as_ref(),
+            shard.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<ClaimBitmap>(),
+        payer = authority
+    )]
+    pub claim_bitmap: AccountLoader<'info, ClaimBitmap>,
+
+    /// The agent or the manager of the program, or a holder of the `Submitter` or `ConfigAdmin` role.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// init claim bitmap handler.
+pub fn init_claim_bitmap_handler(
+    ctx: Context<InitClaimBitmap>,
+    epoch_nr: u64,
+    shard: u64,
+) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    let authority = ctx.accounts.authority.key();
+    require!(
+        !rewards_account.is_paused(PAUSE_SUBMISSIONS),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+
+    // A swept epoch has nothing left to claim, its shards would only hold rent.
+    require!(!epoch_account.is_swept, ErrorCode::EpochSwept);
+
+    // Switching to the bitmap after claims were tracked by claim status
+    // accounts would let those leaves be claimed a second time.
+    require!(
+        epoch_account.claim_bitmap_shards > 0 || epoch_account.num_nodes_claimed == 0,
+        ErrorCode::ClaimTrackingLocked
+    );
+
+    // Shards are allocated in order, so `claim_bitmap_shards` tells which leaves are covered.
+    require!(
+        shard == epoch_account.claim_bitmap_shards,
+        ErrorCode::InvalidClaimBitmapShard
+    );
+
+    let claim_bitmap = &mut ctx.accounts.claim_bitmap.load_init()?;
+    claim_bitmap.epoch = epoch_account.key();
+    claim_bitmap.shard = shard;
+    claim_bitmap.payer = authority;
+
+    epoch_account.claim_bitmap_shards += 1;
+
+    emit!(ClaimBitmapCreated { epoch_nr, shard });
+
+    Ok(())
+}
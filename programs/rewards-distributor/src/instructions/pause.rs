@@ -1,6 +1,5 @@
 use crate::errors::ErrorCode;
-use crate::events::Paused;
-use crate::state::RewardsAccount;
+use crate::state::{RewardsAccount, RoleGrant, PAUSE_ALL};
 use anchor_lang::prelude::*;
 
 /// [rewards_distributor::pause] accounts.
@@ -11,17 +10,26 @@ pub struct Pause<'info> {
     pub manager: Signer<'info>,
 
     /// The [RewardsAccount].
-    #[account(mut, has_one = manager @ ErrorCode::Unauthorized,)]
+    #[account(
+        mut,
+        constraint = rewards_account.can_pause(&manager.key(), role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
     pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
 }
 
 pub fn pause_handler(ctx: Context<Pause>) -> Result<()> {
     let rewards_account = &mut ctx.accounts.rewards_account;
-    require!(!rewards_account.is_paused, ErrorCode::ShouldNotBePaused);
-
-    rewards_account.is_paused = true;
+    // Pausing everything still pauses the operations left running by a partial pause.
+    require!(
+        !rewards_account.is_paused(PAUSE_ALL) || rewards_account.pause_flags != PAUSE_ALL,
+        ErrorCode::ShouldNotBePaused
+    );
 
-    emit!(Paused { is_paused: true });
+    rewards_account.pause(PAUSE_ALL, 0, 0);
 
     Ok(())
 }
@@ -0,0 +1,43 @@
+use crate::errors::ErrorCode;
+use crate::state::{RewardsAccount, RoleGrant, PAUSE_ALL};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::pause_operations] accounts.
+#[derive(Accounts)]
+pub struct PauseOperations<'info> {
+    /// The `guardian`, or a holder of the pauser role.
+    pub pauser: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(
+        mut,
+        constraint = rewards_account.can_pause(&pauser.key(), role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+pub fn pause_operations_handler(
+    ctx: Context<PauseOperations>,
+    flags: u8,
+    reason: u16,
+    expires_at: i64,
+) -> Result<()> {
+    require!(
+        flags != 0 && flags & !PAUSE_ALL == 0,
+        ErrorCode::InvalidPauseFlags
+    );
+    require!(
+        expires_at == 0 || expires_at > Clock::get()?.unix_timestamp,
+        ErrorCode::InvalidPauseExpiry
+    );
+
+    ctx.accounts
+        .rewards_account
+        .pause(flags, reason, expires_at);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::errors::ErrorCode;
+use crate::state::{RewardsAccount, Role, RoleGrant, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::propose_agent] accounts.
+#[derive(Accounts)]
+pub struct ProposeAgent<'info> {
+    // current manager of the program.
+    #[account(mut)]
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&manager.key(), Role::ConfigAdmin, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+/// propose agent handler.
+pub fn propose_agent_handler(
+    ctx: Context<ProposeAgent>,
+    proposed_agent: Pubkey,
+    overlap: i64,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(!rewards_account.is_multisig(), ErrorCode::ProposalRequired);
+    require!(
+        !rewards_account.is_timelocked(),
+        ErrorCode::TimelockRequired
+    );
+
+    rewards_account.propose_agent(proposed_agent, overlap)
+}
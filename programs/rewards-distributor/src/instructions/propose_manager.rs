@@ -1,6 +1,6 @@
 use crate::errors::ErrorCode;
 use crate::events::NewProposedManager;
-use crate::state::RewardsAccount;
+use crate::state::{RewardsAccount, Role, RoleGrant, PAUSE_ADMIN};
 use anchor_lang::prelude::*;
 
 /// [rewards_distributor::propose_manager] accounts.
@@ -11,9 +11,16 @@ pub struct ProposeManager<'info> {
     manager: Signer<'info>,
 
     /// The [RewardsAccount].
-    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&manager.key(), Role::ConfigAdmin, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
     pub rewards_account: Account<'info, RewardsAccount>,
 
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
     /// The [System] program.
     pub system_program: Program<'info, System>,
 }
@@ -26,7 +33,15 @@ pub fn propose_manager_handler(
     // Get a mutable reference to the rewards account from the context.
     let rewards_account = &mut ctx.accounts.rewards_account;
 
-    require!(!rewards_account.is_paused, ErrorCode::ShouldNotBePaused);
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(!rewards_account.is_multisig(), ErrorCode::ProposalRequired);
+    require!(
+        !rewards_account.is_timelocked(),
+        ErrorCode::TimelockRequired
+    );
 
     // Update the admin field of the rewards account to the new admin.
     rewards_account.proposed_manager = proposed_manager;
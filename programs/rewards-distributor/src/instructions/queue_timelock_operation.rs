@@ -0,0 +1,106 @@
+use crate::errors::ErrorCode;
+use crate::events::TimelockOperationQueued;
+use crate::state::{
+    assert_valid_agent_quorum, assert_valid_multisig, ManagerAction, ManagerProposal,
+    RewardsAccount, Role, TimelockAction, TimelockOperation, BPS, PAUSE_ADMIN,
+};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::queue_timelock_operation] accounts.
+#[derive(Accounts)]
+pub struct QueueTimelockOperation<'info> {
+    /// The [RewardsAccount].
+    #[account(mut)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [TimelockOperation] to queue.
+    #[account(
+        init,
+        seeds = [
+            b"TimelockOperation".as_ref(),
+            rewards_account.key().as_ref(),
+            (rewards_account.timelock_count + 1).to_le_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + TimelockOperation::INIT_SPACE,
+        payer = manager
+    )]
+    pub operation: Account<'info, TimelockOperation>,
+
+    /// current manager of the program, or a member of the manager multisig.
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    /// The [ManagerProposal] queueing the operation, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// queue timelock operation handler.
+pub fn queue_timelock_operation_handler(
+    ctx: Context<QueueTimelockOperation>,
+    action: TimelockAction,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    let manager = ctx.accounts.manager.key();
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    if rewards_account.is_multisig() {
+        rewards_account.authorize_manager(
+            &manager,
+            ctx.accounts.proposal.as_deref_mut(),
+            None,
+            Role::ConfigAdmin,
+            &ManagerAction::QueueTimelockOperation {
+                action: action.clone(),
+            },
+        )?;
+    } else {
+        require_keys_eq!(manager, rewards_account.manager, ErrorCode::Unauthorized);
+    }
+
+    match action {
+        TimelockAction::SetTimelockDelay { delay } => {
+            require!(delay >= 0, ErrorCode::InvalidTimelockDelay)
+        }
+        TimelockAction::ProposeAgent { overlap, .. } => {
+            require!(overlap >= 0, ErrorCode::InvalidAgentOverlap)
+        }
+        TimelockAction::SetEmissionSchedule { emission_schedule } => {
+            require!(
+                emission_schedule.decay_bps <= BPS,
+                ErrorCode::InvalidEmissionSchedule
+            )
+        }
+        TimelockAction::SetAgentQuorum { ref agents, quorum } => {
+            assert_valid_agent_quorum(agents, quorum)?
+        }
+        TimelockAction::SetMultisig {
+            ref members,
+            threshold,
+        } => assert_valid_multisig(members, threshold)?,
+        _ => {}
+    }
+
+    rewards_account.timelock_count += 1;
+
+    let operation = &mut ctx.accounts.operation;
+    operation.rewards_account = rewards_account.key();
+    operation.operation_nr = rewards_account.timelock_count;
+    operation.action = action.clone();
+    operation.eta = Clock::get()?.unix_timestamp + rewards_account.timelock_delay;
+
+    emit!(TimelockOperationQueued {
+        operation_nr: operation.operation_nr,
+        action,
+        eta: operation.eta,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::{
+    errors::ErrorCode,
+    events::EpochRejected,
+    state::{EpochAccount, RewardsAccount, Role, RoleGrant, PAUSE_APPROVALS},
+};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::reject_epoch] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct RejectEpoch<'info> {
+    /// The [RewardsAccount]
+    #[account(
+        constraint = rewards_account.has_role(&manager.key(), Role::Approver, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// current manager of the program.
+    pub manager: Signer<'info>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+pub fn reject_epoch_handler(ctx: Context<RejectEpoch>, epoch_nr: u64, reason: u16) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(
+        !epoch_account.is_approved,
+        ErrorCode::EpochShouldNotBeApproved
+    );
+    // A rejected epoch must be resubmitted before it can be reviewed again.
+    require!(!epoch_account.is_rejected, ErrorCode::EpochRejected);
+
+    epoch_account.is_rejected = true;
+    epoch_account.rejection_reason = reason;
+    epoch_account.rejection_count += 1;
+
+    emit!(EpochRejected {
+        epoch_nr,
+        root: epoch_account.hash,
+        reason,
+        rejection_count: epoch_account.rejection_count,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::errors::ErrorCode;
+use crate::events::MintAuthorityReleased;
+use crate::state::{ManagerAction, ManagerProposal, RewardsAccount, Role, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, spl_token_2022::instruction::AuthorityType, Mint, SetAuthority, TokenInterface,
+};
+
+/// [rewards_distributor::release_mint_authority] accounts.
+#[derive(Accounts)]
+pub struct ReleaseMintAuthority<'info> {
+    /// The [RewardsAccount]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] of the action, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// CHECK: PDA holding the mint authority of the mint.
+    #[account(
+        seeds = [
+             b"MintAuthority".as_ref(),
+             rewards_account.key().as_ref()
+        ],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = mint_account.mint_authority == Some(mint_authority.key()).into() @ ErrorCode::InvalidMintAuthority
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// release mint authority handler.
+pub fn release_mint_authority_handler(
+    ctx: Context<ReleaseMintAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    ctx.accounts.rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        None,
+        Role::ConfigAdmin,
+        &ManagerAction::ReleaseMintAuthority {
+            mint: ctx.accounts.mint_account.key(),
+            new_authority,
+        },
+    )?;
+    require!(
+        !ctx.accounts.rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    let rewards_account_key = ctx.accounts.rewards_account.key();
+    let seeds = [
+        b"MintAuthority".as_ref(),
+        rewards_account_key.as_ref(),
+        &[ctx.bumps.mint_authority],
+    ];
+
+    token_interface::set_authority(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.mint_authority.to_account_info(),
+                account_or_mint: ctx.accounts.mint_account.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        AuthorityType::MintTokens,
+        Some(new_authority),
+    )?;
+
+    emit!(MintAuthorityReleased {
+        mint: ctx.accounts.mint_account.key(),
+        new_authority,
+    });
+
+    Ok(())
+}
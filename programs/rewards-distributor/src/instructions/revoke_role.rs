@@ -0,0 +1,49 @@
+use crate::errors::ErrorCode;
+use crate::events::RoleRevoked;
+use crate::state::{ManagerAction, ManagerProposal, RewardsAccount, Role, RoleGrant};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::revoke_role] accounts.
+#[derive(Accounts)]
+#[instruction(role: Role, member: Pubkey)]
+pub struct RevokeRole<'info> {
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] of the action, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// The [RewardsAccount].
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [RoleGrant] to close, its rent going back to the manager.
+    #[account(
+        mut,
+        seeds = [
+            b"RoleGrant".as_ref(),
+            rewards_account.key().as_ref(),
+            [role as u8].as_ref(),
+            member.as_ref()
+        ],
+        bump,
+        close = manager
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+}
+
+/// revoke role handler.
+pub fn revoke_role_handler(ctx: Context<RevokeRole>, role: Role, member: Pubkey) -> Result<()> {
+    ctx.accounts.rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        None,
+        Role::ConfigAdmin,
+        &ManagerAction::RevokeRole { role, member },
+    )?;
+    // Revoking stays possible while paused, e.g. to remove a compromised member.
+    emit!(RoleRevoked { role, member });
+
+    Ok(())
+}
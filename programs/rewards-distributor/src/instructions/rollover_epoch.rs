@@ -0,0 +1,190 @@
+use crate::errors::ErrorCode;
+use crate::events::RewardsRolledOver;
+use crate::state::{
+    EpochAccount, ManagerAction, ManagerProposal, RewardsAccount, Role, RoleGrant, PAUSE_APPROVALS,
+};
+use crate::utils::token;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// [rewards_distributor::rollover_epoch] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64, next_epoch_nr: u64)]
+pub struct RolloverEpoch<'info> {
+    /// The [RewardsAccount]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount] whose unclaimed rewards are rolled over.
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// The [EpochAccount] of the next epoch distributing the mint, receiving the unclaimed rewards.
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             next_epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub next_epoch_account: Account<'info, EpochAccount>,
+
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] of the action, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    /// Epoch ATA
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub epoch_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Next epoch ATA
+    #[account(
+        init_if_needed,
+        payer = manager,
+        associated_token::mint = mint_account,
+        associated_token::authority = next_epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub next_epoch_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The primary mint of the epoch or one of its additional mints.
+    #[account(
+        constraint = epoch_account.mint_index_of(&mint_account.key()).is_some() @ ErrorCode::InvalidMintAccount
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated [Token] program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn rollover_epoch_handler(
+    ctx: Context<RolloverEpoch>,
+    epoch_nr: u64,
+    next_epoch_nr: u64,
+) -> Result<()> {
+    ctx.accounts.rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        ctx.accounts.role_grant.as_deref(),
+        Role::Approver,
+        &ManagerAction::RolloverEpoch {
+            epoch_nr,
+            next_epoch_nr,
+            mint: ctx.accounts.mint_account.key(),
+        },
+    )?;
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(epoch_account.is_approved, ErrorCode::EpochShouldBeApproved);
+    require!(
+        epoch_account.is_past_deadline(Clock::get()?.unix_timestamp),
+        ErrorCode::ClaimDeadlineNotReached
+    );
+
+    // The rewards move forward, to a later epoch that distributes the mint from its vault
+    // and still pays it out.
+    require!(next_epoch_nr > epoch_nr, ErrorCode::InvalidEpochNr);
+    let next_epoch_account = &mut ctx.accounts.next_epoch_account;
+    let mint_index = next_epoch_account
+        .mint_index_of(&ctx.accounts.mint_account.key())
+        .ok_or(ErrorCode::InvalidMintAccount)?;
+    require!(
+        !next_epoch_account.is_minted(mint_index),
+        ErrorCode::InvalidMintAccount
+    );
+    let is_next_mint_swept = match mint_index {
+        0 => next_epoch_account.is_swept,
+        _ => next_epoch_account.bonus_mints[mint_index as usize - 1].is_swept,
+    };
+    require!(!is_next_mint_swept, ErrorCode::EpochSwept);
+
+    // Like a sweep, the rollover closes the epoch for claims of the mint.
+    let swept_mint_index = epoch_account
+        .mint_index_of(&ctx.accounts.mint_account.key())
+        .ok_or(ErrorCode::InvalidMintAccount)?;
+    epoch_account.mark_swept(swept_mint_index)?;
+
+    // Define the seeds for signing the transaction.
+    let rewards_account_key = rewards_account.key();
+    let seeds = [
+        b"EpochAccount".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_account.epoch_nr.to_le_bytes(),
+        &[epoch_account.bump],
+    ];
+
+    let amount = ctx.accounts.epoch_token_account.amount;
+    let net_amount =
+        amount - token::transfer_fee(&ctx.accounts.mint_account.to_account_info(), amount)?;
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.epoch_token_account.to_account_info(),
+                mint: ctx.accounts.mint_account.to_account_info(),
+                to: ctx.accounts.next_epoch_token_account.to_account_info(),
+                authority: epoch_account.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+        ctx.accounts.mint_account.decimals,
+    )?;
+
+    // Recycled rewards were issued by the previous epoch already, they do not count
+    // against the emission schedule. Those of the primary mint count toward what the
+    // approval of the next epoch funds, or are credited to it once approved.
+    let funded_amount = match mint_index {
+        0 if next_epoch_account.is_approved => &mut next_epoch_account.funded_amount,
+        0 => &mut next_epoch_account.rolled_over_amount,
+        _ => &mut next_epoch_account.bonus_mints[mint_index as usize - 1].funded_amount,
+    };
+    *funded_amount += net_amount;
+    let funded_amount = *funded_amount;
+
+    emit!(RewardsRolledOver {
+        epoch_nr,
+        next_epoch_nr: next_epoch_account.epoch_nr,
+        mint: ctx.accounts.mint_account.key(),
+        amount,
+        net_amount,
+        funded_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::errors::ErrorCode;
+use crate::state::{assert_valid_agent_quorum, RewardsAccount, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::set_agent_quorum] accounts.
+#[derive(Accounts)]
+pub struct SetAgentQuorum<'info> {
+    // current manager of the program.
+    #[account(mut)]
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    rewards_account: Account<'info, RewardsAccount>,
+}
+
+pub fn set_agent_quorum_handler(
+    ctx: Context<SetAgentQuorum>,
+    agents: Vec<Pubkey>,
+    quorum: u8,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(!rewards_account.is_multisig(), ErrorCode::ProposalRequired);
+    require!(
+        !rewards_account.is_timelocked(),
+        ErrorCode::TimelockRequired
+    );
+    assert_valid_agent_quorum(&agents, quorum)?;
+
+    rewards_account.set_agent_quorum(agents, quorum);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::errors::ErrorCode;
+use crate::events::ChallengeWindowChanged;
+use crate::state::{RewardsAccount, Role, RoleGrant, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::set_challenge_window] accounts.
+#[derive(Accounts)]
+pub struct SetChallengeWindow<'info> {
+    // current manager of the program.
+    #[account(mut)]
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&manager.key(), Role::ConfigAdmin, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+pub fn set_challenge_window_handler(
+    ctx: Context<SetChallengeWindow>,
+    challenge_window: i64,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(challenge_window >= 0, ErrorCode::InvalidChallengeWindow);
+
+    rewards_account.challenge_window = challenge_window;
+
+    emit!(ChallengeWindowChanged { challenge_window });
+
+    Ok(())
+}
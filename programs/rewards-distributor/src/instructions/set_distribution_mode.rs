@@ -0,0 +1,46 @@
+use crate::errors::ErrorCode;
+use crate::events::DistributionModeChanged;
+use crate::state::{DistributionMode, RewardsAccount, Role, RoleGrant, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::set_distribution_mode] accounts.
+#[derive(Accounts)]
+pub struct SetDistributionMode<'info> {
+    // current manager of the program.
+    #[account(mut)]
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&manager.key(), Role::ConfigAdmin, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+pub fn set_distribution_mode_handler(
+    ctx: Context<SetDistributionMode>,
+    distribution_mode: DistributionMode,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    // Switching modes would reinterpret the leaves of existing epochs.
+    require!(
+        rewards_account.current_epoch_nr == 0,
+        ErrorCode::DistributionModeLocked
+    );
+
+    rewards_account.distribution_mode = distribution_mode;
+
+    emit!(DistributionModeChanged { distribution_mode });
+
+    Ok(())
+}
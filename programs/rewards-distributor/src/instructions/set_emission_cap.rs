@@ -0,0 +1,37 @@
+use crate::errors::ErrorCode;
+use crate::events::EmissionCapChanged;
+use crate::state::{RewardsAccount, Role, RoleGrant, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::set_emission_cap] accounts.
+#[derive(Accounts)]
+pub struct SetEmissionCap<'info> {
+    // current manager of the program.
+    #[account(mut)]
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&manager.key(), Role::ConfigAdmin, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+pub fn set_emission_cap_handler(ctx: Context<SetEmissionCap>, emission_cap: u64) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    rewards_account.emission_cap = emission_cap;
+
+    emit!(EmissionCapChanged { emission_cap });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::errors::ErrorCode;
+use crate::events::EmissionScheduleChanged;
+use crate::state::{EmissionSchedule, RewardsAccount, Role, RoleGrant, BPS, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::set_emission_schedule] accounts.
+#[derive(Accounts)]
+pub struct SetEmissionSchedule<'info> {
+    // current manager of the program.
+    #[account(mut)]
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&manager.key(), Role::ConfigAdmin, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+pub fn set_emission_schedule_handler(
+    ctx: Context<SetEmissionSchedule>,
+    emission_schedule: EmissionSchedule,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(
+        !rewards_account.is_timelocked(),
+        ErrorCode::TimelockRequired
+    );
+    require!(
+        emission_schedule.decay_bps <= BPS,
+        ErrorCode::InvalidEmissionSchedule
+    );
+
+    rewards_account.emission_schedule = Some(emission_schedule);
+
+    emit!(EmissionScheduleChanged { emission_schedule });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::errors::ErrorCode;
+use crate::state::{assert_valid_multisig, RewardsAccount, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::set_manager_multisig] accounts.
+#[derive(Accounts)]
+pub struct SetManagerMultisig<'info> {
+    // current manager of the program.
+    #[account(mut)]
+    manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    rewards_account: Account<'info, RewardsAccount>,
+}
+
+pub fn set_manager_multisig_handler(
+    ctx: Context<SetManagerMultisig>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+    // Once enabled, the multisig is only changed by its own proposals.
+    require!(!rewards_account.is_multisig(), ErrorCode::ProposalRequired);
+    require!(
+        !rewards_account.is_timelocked(),
+        ErrorCode::TimelockRequired
+    );
+    assert_valid_multisig(&members, threshold)?;
+
+    rewards_account.set_multisig(members, threshold);
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::errors::ErrorCode;
+use crate::state::{EpochAccount, RewardsAccount, RootSubmission, PAUSE_SUBMISSIONS};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::submit_epoch_root] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct SubmitEpochRoot<'info> {
+    /// The [RewardsAccount]
+    #[account(
+        constraint = rewards_account.quorum_agents.contains(&agent.key()) @ ErrorCode::Unauthorized
+    )]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump,
+        realloc = epoch_account.space_after_root_submission(&rewards_account, &agent.key()),
+        realloc::payer = agent,
+        realloc::zero = false,
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// One of the quorum agents.
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn submit_epoch_root_handler(
+    ctx: Context<SubmitEpochRoot>,
+    _epoch_nr: u64,
+    root: [u8; 32],
+    max_total_claim: u64,
+    max_num_nodes: u64,
+    mint: Pubkey,
+) -> Result<()> {
+    let rewards_account = &ctx.accounts.rewards_account;
+    let epoch_account = &mut ctx.accounts.epoch_account;
+
+    require!(
+        !rewards_account.is_paused(PAUSE_SUBMISSIONS),
+        ErrorCode::ShouldNotBePaused
+    );
+    require!(
+        !epoch_account.is_approved,
+        ErrorCode::EpochShouldNotBeApproved
+    );
+
+    epoch_account.submit_root(
+        rewards_account,
+        RootSubmission {
+            agent: ctx.accounts.agent.key(),
+            root,
+            max_total_claim,
+            max_num_nodes,
+            mint,
+        },
+    );
+
+    Ok(())
+}
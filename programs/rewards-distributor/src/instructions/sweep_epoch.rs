@@ -0,0 +1,142 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochSwept;
+use crate::state::{
+    EpochAccount, ManagerAction, ManagerProposal, RewardsAccount, Role, RoleGrant, PAUSE_APPROVALS,
+};
+use crate::utils::token;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// [rewards_distributor::sweep_epoch] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct SweepEpoch<'info> {
+    /// The [RewardsAccount]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] of the action, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    /// Epoch ATA
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub epoch_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury token account receiving the unclaimed rewards.
+    #[account(
+        mut,
+        token::mint = mint_account,
+        token::token_program = token_program,
+        constraint = treasury_token_account.owner == rewards_account.treasury @ ErrorCode::InvalidTreasury
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The primary mint of the epoch or one of its additional mints.
+    #[account(
+        constraint = epoch_account.mint_index_of(&mint_account.key()).is_some() @ ErrorCode::InvalidMintAccount
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn sweep_epoch_handler(ctx: Context<SweepEpoch>, epoch_nr: u64) -> Result<()> {
+    ctx.accounts.rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        ctx.accounts.role_grant.as_deref(),
+        Role::Approver,
+        &ManagerAction::SweepEpoch {
+            epoch_nr,
+            mint: ctx.accounts.mint_account.key(),
+        },
+    )?;
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+    require_keys_neq!(
+        rewards_account.treasury,
+        Pubkey::default(),
+        ErrorCode::InvalidTreasury
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(epoch_account.is_approved, ErrorCode::EpochShouldBeApproved);
+    require!(
+        epoch_account.is_past_deadline(Clock::get()?.unix_timestamp),
+        ErrorCode::ClaimDeadlineNotReached
+    );
+
+    // Epochs with several mints are swept once per mint.
+    let mint_index = epoch_account
+        .mint_index_of(&ctx.accounts.mint_account.key())
+        .ok_or(ErrorCode::InvalidMintAccount)?;
+    epoch_account.mark_swept(mint_index)?;
+
+    // Define the seeds for signing the transaction.
+    let rewards_account_key = rewards_account.key();
+    let seeds = [
+        b"EpochAccount".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_account.epoch_nr.to_le_bytes(),
+        &[epoch_account.bump],
+    ];
+
+    let amount = ctx.accounts.epoch_token_account.amount;
+    let net_amount =
+        amount - token::transfer_fee(&ctx.accounts.mint_account.to_account_info(), amount)?;
+
+    // Invoke the transfer instruction on the token program
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.epoch_token_account.to_account_info(),
+                mint: ctx.accounts.mint_account.to_account_info(),
+                to: ctx.accounts.treasury_token_account.to_account_info(),
+                authority: epoch_account.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+        ctx.accounts.mint_account.decimals,
+    )?;
+
+    emit!(EpochSwept {
+        epoch_nr,
+        mint: ctx.accounts.mint_account.key(),
+        amount,
+        net_amount,
+        treasury_token_account: ctx.accounts.treasury_token_account.key(),
+    });
+
+    Ok(())
+}
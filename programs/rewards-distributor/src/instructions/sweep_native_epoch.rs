@@ -0,0 +1,126 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochSwept;
+use crate::state::{
+    EpochAccount, ManagerAction, ManagerProposal, RewardsAccount, Role, RoleGrant, PAUSE_APPROVALS,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+/// [rewards_distributor::sweep_native_epoch] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct SweepNativeEpoch<'info> {
+    /// The [RewardsAccount]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] of the action, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    /// Lamport vault of the epoch.
+    #[account(
+        mut,
+        seeds = [
+             b"EpochVault".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Treasury receiving the unclaimed lamports.
+    #[account(mut, address = rewards_account.treasury @ ErrorCode::InvalidTreasury)]
+    pub treasury: SystemAccount<'info>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn sweep_native_epoch_handler(ctx: Context<SweepNativeEpoch>, epoch_nr: u64) -> Result<()> {
+    ctx.accounts.rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        ctx.accounts.role_grant.as_deref(),
+        Role::Approver,
+        &ManagerAction::SweepEpoch {
+            epoch_nr,
+            mint: Pubkey::default(),
+        },
+    )?;
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_APPROVALS),
+        ErrorCode::ShouldNotBePaused
+    );
+    require_keys_neq!(
+        rewards_account.treasury,
+        Pubkey::default(),
+        ErrorCode::InvalidTreasury
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(epoch_account.is_native, ErrorCode::NotNativeEpoch);
+    require!(epoch_account.is_approved, ErrorCode::EpochShouldBeApproved);
+    require!(
+        epoch_account.is_past_deadline(Clock::get()?.unix_timestamp),
+        ErrorCode::ClaimDeadlineNotReached
+    );
+    epoch_account.mark_swept(0)?;
+
+    // The vault keeps its rent-exempt minimum, recovered when the epoch is closed.
+    let amount = ctx
+        .accounts
+        .vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+
+    let rewards_account_key = rewards_account.key();
+    let epoch_nr_bytes = epoch_nr.to_le_bytes();
+    let seeds = [
+        b"EpochVault".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_nr_bytes,
+        &[ctx.bumps.vault],
+    ];
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+    )?;
+
+    emit!(EpochSwept {
+        epoch_nr,
+        mint: Pubkey::default(),
+        amount,
+        net_amount: amount,
+        treasury_token_account: ctx.accounts.treasury.key(),
+    });
+
+    Ok(())
+}
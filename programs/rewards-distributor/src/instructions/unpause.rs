@@ -1,6 +1,5 @@
 use crate::errors::ErrorCode;
-use crate::events::Paused;
-use crate::state::RewardsAccount;
+use crate::state::{RewardsAccount, Role, RoleGrant, PAUSE_ALL};
 use anchor_lang::prelude::*;
 
 /// [rewards_distributor::unpaused] accounts.
@@ -11,17 +10,26 @@ pub struct UnPause<'info> {
     pub manager: Signer<'info>,
 
     /// The [RewardsAccount].
-    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&manager.key(), Role::Pauser, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
     pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
 }
 
 pub fn unpause_handler(ctx: Context<UnPause>) -> Result<()> {
     let rewards_account = &mut ctx.accounts.rewards_account;
-    require!(rewards_account.is_paused, ErrorCode::ShouldBePaused);
-
-    rewards_account.is_paused = false;
+    require!(
+        rewards_account.is_paused(PAUSE_ALL),
+        ErrorCode::ShouldBePaused
+    );
+    require!(!rewards_account.is_multisig(), ErrorCode::ProposalRequired);
 
-    emit!(Paused { is_paused: false });
+    rewards_account.unpause(PAUSE_ALL);
 
     Ok(())
 }
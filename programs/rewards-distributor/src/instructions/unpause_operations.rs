@@ -0,0 +1,35 @@
+use crate::errors::ErrorCode;
+use crate::state::{RewardsAccount, Role, RoleGrant, PAUSE_ALL};
+use anchor_lang::prelude::*;
+
+/// [rewards_distributor::unpause_operations] accounts.
+#[derive(Accounts)]
+pub struct UnpauseOperations<'info> {
+    // current manager of the program.
+    pub manager: Signer<'info>,
+
+    /// The [RewardsAccount].
+    #[account(
+        mut,
+        constraint = rewards_account.has_role(&manager.key(), Role::Pauser, role_grant.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// [RoleGrant] of the signer, only needed if it does not hold the role by default.
+    #[account(has_one = rewards_account @ ErrorCode::Unauthorized)]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+pub fn unpause_operations_handler(ctx: Context<UnpauseOperations>, flags: u8) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    require!(
+        flags != 0 && flags & !PAUSE_ALL == 0,
+        ErrorCode::InvalidPauseFlags
+    );
+    require!(rewards_account.is_paused(flags), ErrorCode::ShouldBePaused);
+    require!(!rewards_account.is_multisig(), ErrorCode::ProposalRequired);
+
+    rewards_account.unpause(flags);
+
+    Ok(())
+}
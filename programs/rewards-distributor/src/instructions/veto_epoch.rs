@@ -0,0 +1,233 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochVetoed;
+use crate::state::{DistributionMode, EpochAccount, FundingSource, RewardsAccount};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// [rewards_distributor::veto_epoch] accounts.
+///
+/// For every top-up of the epoch, in the order they were recorded, `remaining_accounts`
+/// must contain a token account (writable) of the primary mint owned by its funder.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct VetoEpoch<'info> {
+    /// The [RewardsAccount]
+    #[account(mut)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// The manager or the guardian of the program.
+    pub authority: Signer<'info>,
+
+    /// Epoch ATA
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub epoch_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account of the manager who approved the epoch, receiving the funded tokens back
+    /// when they funded it.
+    #[account(mut,
+        constraint = manager_token_account.owner == epoch_account.approver @ ErrorCode::OwnerMismatch
+    )]
+    pub manager_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury vault of the mint, receiving the funded tokens back when it funded the epoch.
+    #[account(
+        mut,
+        seeds = [
+             b"TreasuryVault".as_ref(),
+             rewards_account.key().as_ref(),
+             mint_account.key().as_ref()
+        ],
+        bump,
+    )]
+    pub treasury_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = epoch_account.mint @ ErrorCode::InvalidMintAccount)]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// The previous [EpochAccount], only needed in [DistributionMode::Cumulative].
+    #[account(
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.saturating_sub(1).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub previous_epoch_account: Option<Account<'info, EpochAccount>>,
+
+    /// Previous epoch ATA, only needed in [DistributionMode::Cumulative].
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = previous_epoch_account,
+        associated_token::token_program = token_program,
+    )]
+    pub previous_epoch_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn veto_epoch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VetoEpoch<'info>>,
+    epoch_nr: u64,
+) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == rewards_account.manager || authority == rewards_account.guardian,
+        ErrorCode::Unauthorized
+    );
+
+    // Only the latest approved epoch can be reverted, so that epochs stay approved in order.
+    require!(
+        epoch_nr == rewards_account.current_approved_epoch,
+        ErrorCode::InvalidEpochNr
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(
+        epoch_account.is_challengeable(Clock::get()?.unix_timestamp),
+        ErrorCode::ChallengeWindowClosed
+    );
+
+    // Revert the epoch to unapproved, so that the agent can correct it.
+    rewards_account.current_approved_epoch = epoch_nr - 1;
+    epoch_account.is_approved = false;
+    epoch_account.claims_open_at = 0;
+    epoch_account.claim_deadline = 0;
+    epoch_account.funded_amount = 0;
+    epoch_account.issued_amount = 0;
+    let top_ups = std::mem::take(&mut epoch_account.top_ups);
+
+    let rewards_account_key = rewards_account.key();
+    let seeds = [
+        b"EpochAccount".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_account.epoch_nr.to_le_bytes(),
+        &[epoch_account.bump],
+    ];
+    let mint_info = ctx.accounts.mint_account.to_account_info();
+    let decimals = ctx.accounts.mint_account.decimals;
+    let mut amount = ctx.accounts.epoch_token_account.amount;
+
+    // In cumulative mode the balance carried over at approval is still owed by the
+    // previous epoch, which becomes the latest approved one again.
+    let carried_over = std::mem::take(&mut epoch_account.carried_over);
+    if rewards_account.distribution_mode == DistributionMode::Cumulative && epoch_nr > 1 {
+        let (Some(_), Some(previous_epoch_token_account)) = (
+            &ctx.accounts.previous_epoch_account,
+            &ctx.accounts.previous_epoch_token_account,
+        ) else {
+            return err!(ErrorCode::InvalidPreviousEpoch);
+        };
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.epoch_token_account.to_account_info(),
+                    mint: mint_info.clone(),
+                    to: previous_epoch_token_account.to_account_info(),
+                    authority: epoch_account.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds[..]]),
+            carried_over,
+            decimals,
+        )?;
+
+        amount -= carried_over;
+    }
+
+    // The rewards rolled over before approval stay, to be funded by the next approval.
+    amount -= epoch_account.rolled_over_amount.min(amount);
+
+    // Top-ups go back to their funders.
+    require!(
+        ctx.remaining_accounts.len() == top_ups.len(),
+        ErrorCode::InvalidRemainingAccounts
+    );
+    let mut refunded_top_ups = 0;
+    for (top_up, funder_token_account) in top_ups.iter().zip(ctx.remaining_accounts) {
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(funder_token_account)?;
+        require_keys_eq!(token_account.owner, top_up.funder, ErrorCode::OwnerMismatch);
+
+        let refund = top_up.amount.min(amount);
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.epoch_token_account.to_account_info(),
+                    mint: mint_info.clone(),
+                    to: funder_token_account.clone(),
+                    authority: epoch_account.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds[..]]),
+            refund,
+            decimals,
+        )?;
+
+        amount -= refund;
+        refunded_top_ups += refund;
+    }
+
+    // The funded tokens go back to where they came from at approval.
+    let to = match epoch_account.funding_source {
+        FundingSource::TreasuryVault => ctx
+            .accounts
+            .treasury_vault
+            .as_ref()
+            .map(|a| a.to_account_info()),
+        FundingSource::Approver => ctx
+            .accounts
+            .manager_token_account
+            .as_ref()
+            .map(|a| a.to_account_info()),
+    }
+    .ok_or(ErrorCode::MissingFundingSource)?;
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.epoch_token_account.to_account_info(),
+                mint: mint_info,
+                to,
+                authority: epoch_account.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+        decimals,
+    )?;
+
+    emit!(EpochVetoed {
+        epoch_nr,
+        root: epoch_account.hash,
+        vetoed_by: authority,
+        amount,
+        refunded_top_ups,
+    });
+
+    Ok(())
+}
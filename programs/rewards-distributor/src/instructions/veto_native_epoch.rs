@@ -0,0 +1,115 @@
+use crate::errors::ErrorCode;
+use crate::events::EpochVetoed;
+use crate::state::{EpochAccount, RewardsAccount};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+/// [rewards_distributor::veto_native_epoch] accounts.
+#[derive(Accounts)]
+#[instruction(epoch_nr: u64)]
+pub struct VetoNativeEpoch<'info> {
+    /// The [RewardsAccount]
+    #[account(mut)]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// The [EpochAccount]
+    #[account(mut,
+        seeds = [
+             b"EpochAccount".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    /// The manager or the guardian of the program.
+    pub authority: Signer<'info>,
+
+    /// CHECK: Manager who approved the epoch, receiving the funded lamports back.
+    #[account(mut, address = epoch_account.approver @ ErrorCode::OwnerMismatch)]
+    pub approver: UncheckedAccount<'info>,
+
+    /// Lamport vault of the epoch.
+    #[account(
+        mut,
+        seeds = [
+             b"EpochVault".as_ref(),
+             rewards_account.key().as_ref(),
+             epoch_nr.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn veto_native_epoch_handler(ctx: Context<VetoNativeEpoch>, epoch_nr: u64) -> Result<()> {
+    let rewards_account = &mut ctx.accounts.rewards_account;
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == rewards_account.manager || authority == rewards_account.guardian,
+        ErrorCode::Unauthorized
+    );
+
+    // Only the latest approved epoch can be reverted, so that epochs stay approved in order.
+    require!(
+        epoch_nr == rewards_account.current_approved_epoch,
+        ErrorCode::InvalidEpochNr
+    );
+
+    let epoch_account = &mut ctx.accounts.epoch_account;
+    require!(epoch_account.is_native, ErrorCode::NotNativeEpoch);
+    require!(
+        epoch_account.is_challengeable(Clock::get()?.unix_timestamp),
+        ErrorCode::ChallengeWindowClosed
+    );
+
+    // Revert the epoch to unapproved, so that the agent can correct it.
+    rewards_account.current_approved_epoch = epoch_nr - 1;
+    epoch_account.is_approved = false;
+    epoch_account.claims_open_at = 0;
+    epoch_account.claim_deadline = 0;
+    epoch_account.funded_amount = 0;
+    epoch_account.issued_amount = 0;
+
+    // The approver gets back what they funded, the vault keeps its rent-exempt minimum.
+    let amount = ctx
+        .accounts
+        .vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+
+    let rewards_account_key = rewards_account.key();
+    let epoch_nr_bytes = epoch_nr.to_le_bytes();
+    let seeds = [
+        b"EpochVault".as_ref(),
+        rewards_account_key.as_ref(),
+        &epoch_nr_bytes,
+        &[ctx.bumps.vault],
+    ];
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.approver.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+    )?;
+
+    emit!(EpochVetoed {
+        epoch_nr,
+        root: epoch_account.hash,
+        vetoed_by: authority,
+        amount,
+        refunded_top_ups: 0,
+    });
+
+    Ok(())
+}
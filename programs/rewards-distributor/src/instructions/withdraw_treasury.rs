@@ -0,0 +1,101 @@
+use crate::errors::ErrorCode;
+use crate::events::TreasuryWithdrawn;
+use crate::state::{ManagerAction, ManagerProposal, RewardsAccount, Role, PAUSE_ADMIN};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// [rewards_distributor::withdraw_treasury] accounts.
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    /// The [RewardsAccount]
+    pub rewards_account: Account<'info, RewardsAccount>,
+
+    /// Treasury vault of the mint.
+    #[account(
+        mut,
+        seeds = [
+             b"TreasuryVault".as_ref(),
+             rewards_account.key().as_ref(),
+             mint_account.key().as_ref()
+        ],
+        bump,
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// current manager of the program, or a member of the manager multisig in multisig mode.
+    pub manager: Signer<'info>,
+
+    /// [ManagerProposal] of the action, only needed in multisig mode.
+    #[account(mut, has_one = rewards_account @ ErrorCode::ProposalMismatch)]
+    pub proposal: Option<Account<'info, ManagerProposal>>,
+
+    /// Token account of the manager or of the treasury receiving the withdrawn tokens.
+    #[account(
+        mut,
+        token::mint = mint_account,
+        token::token_program = token_program,
+        constraint = to.owner == rewards_account.manager
+            || (to.owner == rewards_account.treasury && rewards_account.treasury != Pubkey::default())
+            @ ErrorCode::OwnerMismatch
+    )]
+    pub to: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// SPL Token or Token-2022 program of the mint.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// withdraw treasury handler.
+pub fn withdraw_treasury_handler(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    ctx.accounts.rewards_account.authorize_manager(
+        &ctx.accounts.manager.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        None,
+        Role::ConfigAdmin,
+        &ManagerAction::WithdrawTreasury {
+            mint: ctx.accounts.mint_account.key(),
+            to: ctx.accounts.to.key(),
+            amount,
+        },
+    )?;
+    let rewards_account = &ctx.accounts.rewards_account;
+    require!(
+        !rewards_account.is_paused(PAUSE_ADMIN),
+        ErrorCode::ShouldNotBePaused
+    );
+
+    let rewards_account_key = rewards_account.key();
+    let mint_key = ctx.accounts.mint_account.key();
+    let seeds = [
+        b"TreasuryVault".as_ref(),
+        rewards_account_key.as_ref(),
+        mint_key.as_ref(),
+        &[ctx.bumps.treasury_vault],
+    ];
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.treasury_vault.to_account_info(),
+                mint: ctx.accounts.mint_account.to_account_info(),
+                to: ctx.accounts.to.to_account_info(),
+                authority: ctx.accounts.treasury_vault.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+        ctx.accounts.mint_account.decimals,
+    )?;
+
+    emit!(TreasuryWithdrawn {
+        mint: mint_key,
+        to: ctx.accounts.to.key(),
+        amount,
+    });
+
+    Ok(())
+}
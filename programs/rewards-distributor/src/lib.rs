@@ -7,6 +7,7 @@ mod utils;
 
 use anchor_lang::prelude::*;
 pub use instructions::*;
+pub use state::{DistributionMode, EmissionSchedule, ManagerAction, Role, TimelockAction};
 
 declare_id!("2dUMVSQkKUu1YTUrt5xW1w1A27HmnnsoDhn1QKrYPaCS");
 
@@ -25,7 +26,8 @@ pub mod rewards_distributor {
     }
 
     /// Propose a Pubkey to be the `Manager`.
-    /// Can only be called by the current `Manager`.
+    /// Can only be called by the current `Manager` or a member granted the `ConfigAdmin` role,
+    /// through a proposal in multisig mode.
     ///
     /// # Arguments
     ///
@@ -36,6 +38,8 @@ pub mod rewards_distributor {
     ///
     /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
     /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `ProposalRequired` - Thrown in multisig mode.
+    /// * `TimelockRequired` - Thrown if the timelock is enabled.
     pub fn propose_manager(ctx: Context<ProposeManager>, proposed_manager: Pubkey) -> Result<()> {
         propose_manager_handler(ctx, proposed_manager)
     }
@@ -55,73 +59,1135 @@ pub mod rewards_distributor {
         accept_manager_handler(ctx)
     }
 
+    /// Cancels the pending proposal of a new `Manager`, including while paused.
+    /// Can only be called by the `Manager`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `NoPendingProposal` - Thrown if no manager is proposed.
+    pub fn cancel_proposed_manager(ctx: Context<CancelProposedManager>) -> Result<()> {
+        cancel_proposed_manager_handler(ctx)
+    }
+
     /// Change the current agent.
+    /// Can only be called by the Manager or a member granted the `ConfigAdmin` role, through a
+    /// proposal in multisig mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `new_agent` - Pubkey to set as the agent.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `ProposalRequired` - Thrown in multisig mode.
+    /// * `TimelockRequired` - Thrown if the timelock is enabled.
+    pub fn change_agent(ctx: Context<ChangeAgent>, new_agent: Pubkey) -> Result<()> {
+        change_agent_handler(ctx, new_agent)
+    }
+
+    /// Propose a Pubkey to be the agent. Once it accepts, the current agent can still
+    /// submit epochs for `overlap` seconds.
+    /// Can only be called by the Manager or a member granted the `ConfigAdmin` role, through a
+    /// proposal in multisig mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `proposed_agent` - Pubkey to set as the proposed agent.
+    /// * `overlap` - Number of seconds the current agent keeps submitting, `0` for none.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `ProposalRequired` - Thrown in multisig mode.
+    /// * `TimelockRequired` - Thrown if the timelock is enabled.
+    /// * `InvalidAgentOverlap` - Thrown if the overlap is negative.
+    pub fn propose_agent(
+        ctx: Context<ProposeAgent>,
+        proposed_agent: Pubkey,
+        overlap: i64,
+    ) -> Result<()> {
+        propose_agent_handler(ctx, proposed_agent, overlap)
+    }
+
+    /// Accepts the proposed agent role, starting the overlap period of the previous agent.
+    /// Can only be called by the Proposed agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    pub fn accept_agent(ctx: Context<AcceptAgent>) -> Result<()> {
+        accept_agent_handler(ctx)
+    }
+
+    /// Cancels the pending proposal of a new agent, including while paused.
     /// Can only be called by the Manager.
     ///
     /// # Arguments
     ///
     /// * `ctx` - Context for the instruction.
-    /// * `new_agent` - Pubkey to set as the agent.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `NoPendingProposal` - Thrown if no agent is proposed.
+    pub fn cancel_proposed_agent(ctx: Context<CancelProposedAgent>) -> Result<()> {
+        cancel_proposed_agent_handler(ctx)
+    }
+
+    /// Switches the `Manager` to multisig mode: approving epochs, changing or proposing the
+    /// agent, setting the agent quorum, proposing a manager and unpausing then go through proposals
+    /// approved by `threshold` of the `members`. Once enabled, the multisig is changed by its own proposals.
+    /// While the timelock is enabled, the multisig is changed through a timelock operation.
+    /// Can only be called by the `Manager`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `members` - Members of the manager multisig, up to 10.
+    /// * `threshold` - Number of member approvals a proposal needs to execute.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `ProposalRequired` - Thrown if the multisig is already enabled.
+    /// * `TimelockRequired` - Thrown if the timelock is enabled.
+    /// * `InvalidMultisig` - Thrown if the members are duplicated or too many, or the threshold is out of range.
+    pub fn set_manager_multisig(
+        ctx: Context<SetManagerMultisig>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        set_manager_multisig_handler(ctx, members, threshold)
+    }
+
+    /// Switches to quorum mode: epochs are then added and corrected by the `agents`, and
+    /// only approved once `quorum` of them submitted the identical root. Passing no agents
+    /// and a zero quorum switches back to the single `Agent`. While the timelock is enabled,
+    /// the agent quorum is changed through a timelock operation.
+    /// Can only be called by the `Manager`, through a proposal in multisig mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `agents` - Quorum agents, up to 8.
+    /// * `quorum` - Number of identical root submissions an epoch needs to be approved.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `ProposalRequired` - Thrown in multisig mode.
+    /// * `TimelockRequired` - Thrown if the timelock is enabled.
+    /// * `InvalidAgentQuorum` - Thrown if the agents are duplicated or too many, or the quorum is out of range.
+    pub fn set_agent_quorum(
+        ctx: Context<SetAgentQuorum>,
+        agents: Vec<Pubkey>,
+        quorum: u8,
+    ) -> Result<()> {
+        set_agent_quorum_handler(ctx, agents, quorum)
+    }
+
+    /// Creates a proposal of the manager multisig, approved by its creator. It executes once
+    /// it reaches the threshold, except epoch approvals, executed by the approve instruction
+    /// of the epoch with the proposal.
+    /// Can only be called by a member of the manager multisig.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `action` - The proposed action.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `InvalidMultisig` - Thrown if the proposal changes the multisig to an invalid one.
+    /// * `InvalidAgentQuorum` - Thrown if the proposal sets an invalid agent quorum.
+    /// * `InvalidAgentOverlap` - Thrown if the proposal proposes an agent with a negative overlap.
+    /// * `TimelockRequired` - Thrown if the proposal executes a change the timelock must queue.
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: ManagerAction) -> Result<()> {
+        create_proposal_handler(ctx, action)
+    }
+
+    /// Approves a proposal of the manager multisig, and executes it once it reaches the
+    /// threshold, except the actions executed by their own instruction, such as epoch approvals.
+    /// Can only be called by a member of the manager multisig.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `proposal_nr` - The number of the proposal to approve.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ProposalAlreadyExecuted` - Thrown if the proposal was already executed.
+    /// * `ShouldNotBePaused` - Thrown if the proposal executes an action not allowed while paused.
+    /// * `ShouldBePaused` - Thrown if the proposal unpauses the protocol while it is not paused.
+    /// * `TimelockRequired` - Thrown if the proposal executes a change the timelock must queue.
+    pub fn approve_proposal(ctx: Context<ApproveProposal>, proposal_nr: u64) -> Result<()> {
+        approve_proposal_handler(ctx, proposal_nr)
+    }
+
+    /// Grants a role to a member, on top of its default holder: `Submitter` (the `Agent`),
+    /// `Approver`, `Pauser`, `ConfigAdmin` (the `Manager`) or `Funder` (the `Manager` and the funder).
+    /// Only the `Pauser` role can be granted while the timelock is enabled.
+    /// Can only be called by the `Manager`. In multisig mode, it is called by a member of the
+    /// manager multisig with the proposal granting the role, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `role` - The role to grant.
+    /// * `member` - Pubkey to grant the role to.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal is not for this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    /// * `TimelockRequired` - Thrown if a role other than `Pauser` is granted while the
+    ///   timelock is enabled.
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+        grant_role_handler(ctx, role, member)
+    }
+
+    /// Revokes a role granted to a member, including while paused.
+    /// Can only be called by the `Manager`. In multisig mode, it is called by a member of the
+    /// manager multisig with the proposal revoking the role, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `role` - The role to revoke.
+    /// * `member` - Pubkey to revoke the role from.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal is not for this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role, member: Pubkey) -> Result<()> {
+        revoke_role_handler(ctx, role, member)
+    }
+
+    /// Queues a sensitive change (changing or proposing the agent, proposing a manager,
+    /// setting the treasury, the emission schedule, the agent quorum, the manager multisig or
+    /// the timelock delay), executable once the current timelock delay has passed.
+    /// While the delay is non-zero, these changes can only be made through the queue.
+    /// Can only be called by the `Manager`. In multisig mode, it is called by a member of the
+    /// manager multisig with the proposal queueing the action, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `action` - The change to queue.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidTimelockDelay` - Thrown if the queued delay is negative.
+    /// * `InvalidAgentOverlap` - Thrown if the queued agent overlap is negative.
+    /// * `InvalidEmissionSchedule` - Thrown if the queued decay is over 10000 basis points.
+    /// * `InvalidAgentQuorum` - Thrown if the queued agent quorum is invalid.
+    /// * `InvalidMultisig` - Thrown if the queued manager multisig is invalid.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal does not queue this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    pub fn queue_timelock_operation(
+        ctx: Context<QueueTimelockOperation>,
+        action: TimelockAction,
+    ) -> Result<()> {
+        queue_timelock_operation_handler(ctx, action)
+    }
+
+    /// Executes a queued timelock operation once its eta has passed.
+    /// Can be called by anyone.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `operation_nr` - The number of the operation to execute.
+    ///
+    /// # Errors
+    ///
+    /// * `TimelockOperationNotPending` - Thrown if the operation was executed or cancelled.
+    /// * `TimelockNotReady` - Thrown if the eta of the operation has not passed.
+    /// * `ShouldNotBePaused` - Thrown if the operation changes the agent, the manager or the
+    ///   treasury while the protocol is paused.
+    /// * `InvalidAgentOverlap` - Thrown if the operation proposes an agent with a negative overlap.
+    pub fn execute_timelock_operation(
+        ctx: Context<ExecuteTimelockOperation>,
+        operation_nr: u64,
+    ) -> Result<()> {
+        execute_timelock_operation_handler(ctx, operation_nr)
+    }
+
+    /// Cancels a queued timelock operation, including while paused.
+    /// Can only be called by the `Manager`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `operation_nr` - The number of the operation to cancel.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `TimelockOperationNotPending` - Thrown if the operation was executed or cancelled.
+    pub fn cancel_timelock_operation(
+        ctx: Context<CancelTimelockOperation>,
+        operation_nr: u64,
+    ) -> Result<()> {
+        cancel_timelock_operation_handler(ctx, operation_nr)
+    }
+
+    /// Sets how the merkle leaves are interpreted.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role,
+    /// before the first epoch is added.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `distribution_mode` - The new distribution mode.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `DistributionModeLocked` - Thrown if an epoch was already added.
+    pub fn set_distribution_mode(
+        ctx: Context<SetDistributionMode>,
+        distribution_mode: DistributionMode,
+    ) -> Result<()> {
+        set_distribution_mode_handler(ctx, distribution_mode)
+    }
+
+    /// Sets how long epochs can be claimed after their approval.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role,
+    /// applies to epochs approved afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `claim_window` - Number of seconds an epoch can be claimed, 0 for no deadline.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidClaimWindow` - Thrown if the claim window is negative.
+    pub fn set_claim_window(ctx: Context<SetClaimWindow>, claim_window: i64) -> Result<()> {
+        set_claim_window_handler(ctx, claim_window)
+    }
+
+    /// Sets the owner of the token accounts receiving swept rewards.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `treasury` - Pubkey to set as the treasury.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `TimelockRequired` - Thrown if the timelock is enabled.
+    pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+        set_treasury_handler(ctx, treasury)
+    }
+
+    /// Sets how long approved epochs can be vetoed before their claims open.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role,
+    /// applies to epochs approved afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `challenge_window` - Number of seconds between approval and claim opening, 0 for none.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidChallengeWindow` - Thrown if the challenge window is negative.
+    pub fn set_challenge_window(
+        ctx: Context<SetChallengeWindow>,
+        challenge_window: i64,
+    ) -> Result<()> {
+        set_challenge_window_handler(ctx, challenge_window)
+    }
+
+    /// Sets who can veto approved epochs during their challenge window, besides the `Manager`.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `guardian` - Pubkey to set as the guardian.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        set_guardian_handler(ctx, guardian)
+    }
+
+    /// Sets who can top up approved epochs with `fund_epoch`, besides the `Manager`.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `funder` - Pubkey to set as the funder.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    pub fn set_funder(ctx: Context<SetFunder>, funder: Pubkey) -> Result<()> {
+        set_funder_handler(ctx, funder)
+    }
+
+    /// Sets the maximum amount claims of epochs in mint mode can mint in total.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `emission_cap` - Maximum amount, in base units, to mint across epochs.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    pub fn set_emission_cap(ctx: Context<SetEmissionCap>, emission_cap: u64) -> Result<()> {
+        set_emission_cap_handler(ctx, emission_cap)
+    }
+
+    /// Sets the emission schedule capping what token epochs of its mint issue, checked when
+    /// approving and topping up epochs. While the timelock is enabled, the schedule can only
+    /// be changed through a timelock operation.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `emission_schedule` - Allowance per epoch, starting epoch and decay.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `TimelockRequired` - Thrown if the timelock is enabled.
+    /// * `InvalidEmissionSchedule` - Thrown if the decay is over 10000 basis points.
+    pub fn set_emission_schedule(
+        ctx: Context<SetEmissionSchedule>,
+        emission_schedule: EmissionSchedule,
+    ) -> Result<()> {
+        set_emission_schedule_handler(ctx, emission_schedule)
+    }
+
+    /// Hands the mint authority of a mint from the `Manager` to the program PDA,
+    /// so that epochs of the mint can be approved in mint mode.
+    /// Can only be called by the `Manager`, holding the mint authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidMintAuthority` - Thrown if the `Manager` is not the mint authority.
+    pub fn accept_mint_authority(ctx: Context<AcceptMintAuthority>) -> Result<()> {
+        accept_mint_authority_handler(ctx)
+    }
+
+    /// Hands the mint authority of a mint from the program PDA to `new_authority`.
+    /// Can only be called by the `Manager`. In multisig mode, it is called by a member of the
+    /// manager multisig with the proposal releasing the mint authority, which it approves and
+    /// executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `new_authority` - Pubkey to set as the mint authority.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidMintAuthority` - Thrown if the program PDA is not the mint authority.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal is not for this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    pub fn release_mint_authority(
+        ctx: Context<ReleaseMintAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        release_mint_authority_handler(ctx, new_authority)
+    }
+
+    /// Sets the merkle root for the claiming process.
+    /// Can only be called by the `Agent` or a member granted the `Submitter` role. In quorum
+    /// mode, it is called by a quorum agent and counts as its root submission.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `bump` - Bump seed used for Program Derived Address (PDA) generation.
+    /// * `root` - Root of the merkle tree.
+    /// * `max_total_claim` - Total amount, in base units, the merkle tree pays in the primary mint.
+    /// * `max_num_nodes` - Number of leaves of the merkle tree.
+    /// * `metadata_uri` - Location of the distribution file listing the leaves.
+    /// * `distribution_hash` - Hash of the distribution file.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `PreviousEpochIsNotApproved` - Thrown if the previous epoch is not approved.
+    /// * `InvalidEpochTotals` - Thrown if a declared total is zero.
+    /// * `MetadataUriTooLong` - Thrown if the metadata URI is longer than 200 bytes.
+    /// * `NonTransferableMint`, `PermanentDelegateMint`, `TransferHookMint`, `ConfidentialTransferMint` -
+    ///   Thrown if the mint has a Token-2022 extension the program does not support.
+    pub fn add_epoch(
+        ctx: Context<AddEpoch>,
+        bump: u8,
+        root: [u8; 32],
+        max_total_claim: u64,
+        max_num_nodes: u64,
+        metadata_uri: String,
+        distribution_hash: [u8; 32],
+    ) -> Result<()> {
+        add_epoch_handler(
+            ctx,
+            bump,
+            root,
+            max_total_claim,
+            max_num_nodes,
+            metadata_uri,
+            distribution_hash,
+        )
+    }
+
+    /// Corrects the merkle root for a specific epoch and the mint if needed, discarding the
+    /// root submissions of the epoch.
+    /// Can only be called by the `Agent` or a member granted the `Submitter` role, only
+    /// while the epoch is not approved. In quorum mode, it is called by a quorum agent and
+    /// counts as its root submission; it only changes the epoch once the epoch is rejected,
+    /// and is otherwise recorded as a submission, diverging if it differs from the epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `root` - Corrected root of the merkle tree.
+    /// * `epoch_nr` - The epoch number to correct.
+    /// * `max_total_claim` - Corrected total amount, in base units, the merkle tree pays in the primary mint.
+    /// * `max_num_nodes` - Corrected number of leaves of the merkle tree.
+    /// * `metadata_uri` - Corrected location of the distribution file, the account is resized to fit it.
+    /// * `distribution_hash` - Corrected hash of the distribution file.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `EpochShouldNotBeApproved` - Thrown if the epoch is approved.
+    /// * `InvalidEpochTotals` - Thrown if a declared total is zero.
+    /// * `MetadataUriTooLong` - Thrown if the metadata URI is longer than 200 bytes.
+    /// * `NonTransferableMint`, `PermanentDelegateMint`, `TransferHookMint`, `ConfidentialTransferMint` -
+    ///   Thrown if the mint has a Token-2022 extension the program does not support.
+    /// * `InvalidMintAccount` - Thrown if the mint changes while rewards were rolled over into the epoch.
+    pub fn correct_epoch(
+        ctx: Context<CorrectEpoch>,
+        epoch_nr: u64,
+        root: [u8; 32],
+        max_total_claim: u64,
+        max_num_nodes: u64,
+        metadata_uri: String,
+        distribution_hash: [u8; 32],
+    ) -> Result<()> {
+        correct_epoch_handler(
+            ctx,
+            epoch_nr,
+            root,
+            max_total_claim,
+            max_num_nodes,
+            metadata_uri,
+            distribution_hash,
+        )
+    }
+
+    /// Submits the root of an epoch as computed by a quorum agent, replacing its previous
+    /// submission. A root, totals or mint diverging from the epoch are counted on the epoch.
+    /// Can only be called by a quorum agent, only while the epoch is not approved.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to submit the root of.
+    /// * `root` - Root of the merkle tree computed by the agent.
+    /// * `max_total_claim` - Total amount, in base units, of the merkle tree computed by the agent.
+    /// * `max_num_nodes` - Number of leaves of the merkle tree computed by the agent.
+    /// * `mint` - Mint of the epoch computed by the agent, the default pubkey for native SOL.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `EpochShouldNotBeApproved` - Thrown if the epoch is approved.
+    pub fn submit_epoch_root(
+        ctx: Context<SubmitEpochRoot>,
+        epoch_nr: u64,
+        root: [u8; 32],
+        max_total_claim: u64,
+        max_num_nodes: u64,
+        mint: Pubkey,
+    ) -> Result<()> {
+        submit_epoch_root_handler(ctx, epoch_nr, root, max_total_claim, max_num_nodes, mint)
+    }
+
+    /// Rejects a submitted epoch, which the `Agent` must then resubmit with `correct_epoch`
+    /// before it can be approved.
+    /// Can only be called by the `Manager` or a member granted the `Approver` role, only
+    /// while the epoch is not approved.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to reject.
+    /// * `reason` - Reason code of the rejection, defined off-chain.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `EpochShouldNotBeApproved` - Thrown if the epoch is approved.
+    /// * `EpochRejected` - Thrown if the epoch was already rejected and not resubmitted.
+    pub fn reject_epoch(ctx: Context<RejectEpoch>, epoch_nr: u64, reason: u16) -> Result<()> {
+        reject_epoch_handler(ctx, epoch_nr, reason)
+    }
+
+    /// Adds a mint distributed by an epoch on top of its primary mint, from its own epoch ATA.
+    /// Leaves paying this mint carry its index, starting at 1 for the first added mint.
+    /// Can only be called by the `Agent` or a member granted the `Submitter` role, only
+    /// while the epoch is not approved. Increments the correction count of the epoch, so
+    /// an approval reviewed before the mint was added fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to add the mint to.
     ///
     /// # Errors
     ///
     /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
     /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
-    pub fn change_agent(ctx: Context<ChangeAgent>, new_agent: Pubkey) -> Result<()> {
-        change_agent_handler(ctx, new_agent)
+    /// * `InvalidDistributionMode` - Thrown if the distribution mode is not `PerEpoch`.
+    /// * `EpochShouldNotBeApproved` - Thrown if the epoch is approved.
+    /// * `DuplicateEpochMint` - Thrown if the epoch already distributes the mint.
+    /// * `TooManyEpochMints` - Thrown if the epoch cannot distribute more mints.
+    pub fn add_epoch_mint(ctx: Context<AddEpochMint>, epoch_nr: u64) -> Result<()> {
+        add_epoch_mint_handler(ctx, epoch_nr)
     }
 
-    /// Sets the merkle root for the claiming process.
-    /// Can only be called by the `Manager`.
+    /// Deposits tokens in the treasury vault of the mint, which funds the epochs at approval.
+    /// The vault is created on the first deposit. Can be called by anyone.
     ///
     /// # Arguments
     ///
     /// * `ctx` - Context for the instruction.
-    /// * `bump` - Bump seed used for Program Derived Address (PDA) generation.
-    /// * `root` - Root of the merkle tree.
+    /// * `amount` - The amount of tokens, in base units, the treasury vault must receive.
+    ///
+    /// # Errors
+    ///
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    pub fn deposit_treasury(ctx: Context<DepositTreasury>, amount: u64) -> Result<()> {
+        deposit_treasury_handler(ctx, amount)
+    }
+
+    /// Withdraws tokens from the treasury vault of the mint to a token account of the
+    /// `Manager` or of the treasury.
+    /// Can only be called by the `Manager`. In multisig mode, it is called by a member of the
+    /// manager multisig with the proposal withdrawing the tokens, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `amount` - The amount of tokens, in base units, to withdraw.
     ///
     /// # Errors
     ///
     /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `OwnerMismatch` - Thrown if `to` is not owned by the `Manager` or the treasury.
     /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
-    /// * `PreviousEpochIsNotApproved` - Thrown if the previous epoch is not approved.
-    pub fn add_epoch(ctx: Context<AddEpoch>, bump: u8, root: [u8; 32]) -> Result<()> {
-        add_epoch_handler(ctx, bump, root)
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal is not for this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        withdraw_treasury_handler(ctx, amount)
     }
 
-    /// Corrects the merkle root for a specific epoch and the mint if needed.
-    /// Can only be called by the `Agent` only while the epoch is not approved.
+    /// Tops up the epoch ATA of the primary mint of an approved epoch, up to what it lacks
+    /// to pay the declared total. The top-up is refunded to the funder if the epoch is vetoed.
+    /// Can only be called by the `Manager`, the funder or a member granted the `Funder` role.
     ///
     /// # Arguments
     ///
     /// * `ctx` - Context for the instruction.
-    /// * `root` - Corrected root of the merkle tree.
-    /// * `epoch_nr` - The epoch number to correct.
+    /// * `epoch_nr` - The epoch number to fund.
+    /// * `amount` - The amount of tokens, in base units, the epoch ATA must receive.
     ///
     /// # Errors
     ///
     /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
     /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
-    /// * `EpochShouldNotBeApproved` - Thrown if the epoch is approved.
-    pub fn correct_epoch(ctx: Context<CorrectEpoch>, epoch_nr: u64, root: [u8; 32]) -> Result<()> {
-        correct_epoch_handler(ctx, epoch_nr, root)
+    /// * `EpochShouldBeApproved` - Thrown if the epoch is not approved.
+    /// * `EpochSwept` - Thrown if the epoch was swept.
+    /// * `InvalidMintAccount` - Thrown if the mint is not the primary mint of the epoch.
+    /// * `EmissionScheduleExceeded` - Thrown if the epoch issues more than the emission schedule allows.
+    /// * `MintModeEpoch` - Thrown if the claims of the epoch are minted.
+    /// * `EpochShortfallExceeded` - Thrown if the epoch ATA would hold more than the epoch still owes.
+    /// * `TooManyEpochTopUps` - Thrown if the epoch cannot record top-ups from another funder.
+    pub fn fund_epoch(ctx: Context<FundEpoch>, epoch_nr: u64, amount: u64) -> Result<()> {
+        fund_epoch_handler(ctx, epoch_nr, amount)
     }
 
-    /// Approves the epoch for distribution.
-    /// Can only be called by the `Manager`.
+    /// Funds the epoch ATA of a mint added with `add_epoch_mint`, once the epoch is approved.
+    /// Can only be called by the `Manager`, the funder or a member granted the `Funder` role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to fund.
+    /// * `mint_index` - Index of the mint in the leaves, starting at 1.
+    /// * `amount` - The amount of tokens, in base units, the epoch ATA must receive.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `EpochShouldBeApproved` - Thrown if the epoch is not approved.
+    /// * `EpochSwept` - Thrown if the epoch or the mint was swept.
+    /// * `InvalidMintIndex` - Thrown if the epoch has no added mint at `mint_index`.
+    /// * `InvalidMintAccount` - Thrown if the mint is not the one at `mint_index`.
+    pub fn fund_epoch_mint(
+        ctx: Context<FundEpochMint>,
+        epoch_nr: u64,
+        mint_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        fund_epoch_mint_handler(ctx, epoch_nr, mint_index, amount)
+    }
+
+    /// Allocates a [ClaimBitmap] shard for an epoch, so its claims flip bits instead of
+    /// creating a [ClaimStatus] account each. Shard `n` tracks the leaves
+    /// `n * LEAVES_PER_CLAIM_BITMAP..(n + 1) * LEAVES_PER_CLAIM_BITMAP` and shards are
+    /// allocated in order, usually right after `add_epoch` or around `approve_epoch`.
+    /// Can only be called by the `Agent`, the `Manager` or a member granted the `Submitter`
+    /// or `ConfigAdmin` role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number the bitmap belongs to.
+    /// * `shard` - Index of the shard to allocate.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `EpochSwept` - Thrown if the epoch was swept.
+    /// * `ClaimTrackingLocked` - Thrown if claims were already tracked by claim status accounts.
+    /// * `InvalidClaimBitmapShard` - Thrown if the shard is not the next one to allocate.
+    pub fn init_claim_bitmap(
+        ctx: Context<InitClaimBitmap>,
+        epoch_nr: u64,
+        shard: u64,
+    ) -> Result<()> {
+        init_claim_bitmap_handler(ctx, epoch_nr, shard)
+    }
+
+    /// Approves the epoch for distribution and funds its ATA with the declared total,
+    /// from the treasury vault of the mint if passed, from the `Manager` otherwise.
+    /// In `Cumulative` mode the balance carried over from the previous epoch counts towards it.
+    /// Can only be called by the `Manager` or a member granted the `Approver` role. In multisig
+    /// mode, it is called by a member of the manager multisig with the proposal approving the
+    /// epoch, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to approve.
+    /// * `amount` - The amount, in base units, to be approved for distribution.
+    /// * `expected_root` - Root of the merkle tree the manager reviewed.
+    /// * `expected_correction_count` - Number of corrections of the epoch the manager reviewed.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `EpochChangedSinceReview` - Thrown if the epoch was corrected since the manager reviewed it.
+    /// * `EpochRejected` - Thrown if the epoch was rejected and not resubmitted.
+    /// * `EpochTotalMismatch` - Thrown if the amount is not the declared total of the epoch.
+    /// * `EpochOverfunded` - Thrown if the carried or rolled over balance exceeds the declared total.
+    /// * `MissingFundingSource` - Thrown if neither the treasury vault nor the manager ATA is passed.
+    /// * `EmissionScheduleExceeded` - Thrown if the epoch issues more than the emission schedule allows.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal does not approve the epoch with these arguments.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    /// * `AgentQuorumNotReached` - Thrown if too few quorum agents submitted the root of the epoch.
+    pub fn approve_epoch(
+        ctx: Context<ApproveEpoch>,
+        epoch_nr: u64,
+        amount: u64,
+        expected_root: [u8; 32],
+        expected_correction_count: u64,
+    ) -> Result<()> {
+        approve_epoch_handler(
+            ctx,
+            epoch_nr,
+            amount,
+            expected_root,
+            expected_correction_count,
+        )
+    }
+
+    /// Approves a native SOL epoch and funds its lamport vault.
+    /// Can only be called by the `Manager` or a member granted the `Approver` role. In multisig
+    /// mode, it is called by a member of the manager multisig with the proposal approving the
+    /// epoch, which it approves and executes.
     ///
     /// # Arguments
     ///
     /// * `ctx` - Context for the instruction.
     /// * `epoch_nr` - The epoch number to approve.
-    /// * `amount` - The amount to be approved for distribution.
+    /// * `amount` - The amount of lamports to be approved for distribution.
+    /// * `expected_root` - Root of the merkle tree the manager reviewed.
+    /// * `expected_correction_count` - Number of corrections of the epoch the manager reviewed.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `EpochChangedSinceReview` - Thrown if the epoch was corrected since the manager reviewed it.
+    /// * `EpochRejected` - Thrown if the epoch was rejected and not resubmitted.
+    /// * `InvalidDistributionMode` - Thrown if the distribution mode is not `PerEpoch`.
+    /// * `NotNativeEpoch` - Thrown if the epoch distributes tokens.
+    /// * `EpochTotalMismatch` - Thrown if the amount is not the declared total of the epoch.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal does not approve the epoch with these arguments.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    /// * `AgentQuorumNotReached` - Thrown if too few quorum agents submitted the root of the epoch.
+    pub fn approve_native_epoch(
+        ctx: Context<ApproveNativeEpoch>,
+        epoch_nr: u64,
+        amount: u64,
+        expected_root: [u8; 32],
+        expected_correction_count: u64,
+    ) -> Result<()> {
+        approve_native_epoch_handler(
+            ctx,
+            epoch_nr,
+            amount,
+            expected_root,
+            expected_correction_count,
+        )
+    }
+
+    /// Approves an epoch in mint mode: nothing is funded, claims of the primary mint
+    /// mint the tokens with the program PDA, up to the declared total of the epoch
+    /// and the emission cap.
+    /// Can only be called by the `Manager` or a member granted the `Approver` role. In multisig
+    /// mode, it is called by a member of the manager multisig with the proposal approving the
+    /// epoch, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to approve.
+    /// * `amount` - The amount, in base units, to be approved for distribution.
+    /// * `expected_root` - Root of the merkle tree the manager reviewed.
+    /// * `expected_correction_count` - Number of corrections of the epoch the manager reviewed.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `EpochChangedSinceReview` - Thrown if the epoch was corrected since the manager reviewed it.
+    /// * `EpochRejected` - Thrown if the epoch was rejected and not resubmitted.
+    /// * `InvalidDistributionMode` - Thrown if the distribution mode is not `PerEpoch`.
+    /// * `InvalidMintAuthority` - Thrown if the program PDA is not the mint authority.
+    /// * `EpochTotalMismatch` - Thrown if the amount is not the declared total of the epoch.
+    /// * `EmissionScheduleExceeded` - Thrown if the epoch issues more than the emission schedule allows.
+    /// * `EpochOverfunded` - Thrown if rewards were rolled over into the epoch.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal does not approve the epoch with these arguments.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    /// * `AgentQuorumNotReached` - Thrown if too few quorum agents submitted the root of the epoch.
+    pub fn approve_mint_epoch(
+        ctx: Context<ApproveMintEpoch>,
+        epoch_nr: u64,
+        amount: u64,
+        expected_root: [u8; 32],
+        expected_correction_count: u64,
+    ) -> Result<()> {
+        approve_mint_epoch_handler(
+            ctx,
+            epoch_nr,
+            amount,
+            expected_root,
+            expected_correction_count,
+        )
+    }
+
+    /// Vetoes an approved epoch during its challenge window: the funded tokens go back to the
+    /// treasury vault of the mint or to the `Manager` who approved the epoch, whichever funded
+    /// it, and the epoch is unapproved, so that the `Agent` can correct it.
+    /// In `Cumulative` mode the balance carried over from the previous epoch at approval goes
+    /// back to it.
+    /// Top-ups go back to their funders, whose token accounts are passed as `remaining_accounts`.
+    /// Can only be called by the `Manager` or the `Guardian`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to veto.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `InvalidEpochNr` - Thrown if the epoch is not the latest approved one.
+    /// * `ChallengeWindowClosed` - Thrown if the epoch is not in its challenge window.
+    /// * `MissingFundingSource` - Thrown if the account that funded the epoch is not passed.
+    /// * `OwnerMismatch` - Thrown if the manager token account is not owned by the approver of the epoch.
+    /// * `InvalidRemainingAccounts` - Thrown if a token account is not passed for every top-up.
+    /// * `OwnerMismatch` - Thrown if a token account is not owned by the funder of its top-up.
+    pub fn veto_epoch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VetoEpoch<'info>>,
+        epoch_nr: u64,
+    ) -> Result<()> {
+        veto_epoch_handler(ctx, epoch_nr)
+    }
+
+    /// Vetoes an approved native SOL epoch during its challenge window: the funded lamports go
+    /// back to the `Manager` who approved the epoch and the epoch is unapproved, so that the
+    /// `Agent` can correct it.
+    /// Can only be called by the `Manager` or the `Guardian`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to veto.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `InvalidEpochNr` - Thrown if the epoch is not the latest approved one.
+    /// * `NotNativeEpoch` - Thrown if the epoch distributes tokens.
+    /// * `ChallengeWindowClosed` - Thrown if the epoch is not in its challenge window.
+    pub fn veto_native_epoch(ctx: Context<VetoNativeEpoch>, epoch_nr: u64) -> Result<()> {
+        veto_native_epoch_handler(ctx, epoch_nr)
+    }
+
+    /// Transfers the unclaimed rewards of an epoch to the treasury once its claim deadline
+    /// has passed, and closes the epoch for claims. Epochs with several mints are swept
+    /// once per mint, each only once.
+    /// Can only be called by the `Manager` or a member granted the `Approver` role.
+    /// In multisig mode, it is called by a member of the manager multisig with the proposal
+    /// sweeping the epoch, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to sweep.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidMintAccount` - Thrown if the epoch does not distribute the mint.
+    /// * `ClaimDeadlineNotReached` - Thrown if the claim deadline has not passed.
+    /// * `EpochSwept` - Thrown if the mint of the epoch was already swept.
+    /// * `InvalidTreasury` - Thrown if the treasury is unset or does not own the token account.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal is not for this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    pub fn sweep_epoch(ctx: Context<SweepEpoch>, epoch_nr: u64) -> Result<()> {
+        sweep_epoch_handler(ctx, epoch_nr)
+    }
+
+    /// Transfers the unclaimed lamports of a native SOL epoch to the treasury once its claim
+    /// deadline has passed, and closes the epoch for claims. The vault keeps its rent-exempt
+    /// minimum until the epoch is closed.
+    /// Can only be called by the `Manager` or a member granted the `Approver` role.
+    /// In multisig mode, it is called by a member of the manager multisig with the proposal
+    /// sweeping the epoch, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to sweep.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `NotNativeEpoch` - Thrown if the epoch distributes tokens.
+    /// * `EpochShouldBeApproved` - Thrown if the epoch is not approved.
+    /// * `ClaimDeadlineNotReached` - Thrown if the claim deadline has not passed.
+    /// * `EpochSwept` - Thrown if the epoch was already swept.
+    /// * `InvalidTreasury` - Thrown if the treasury is unset or not the passed account.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal is not for this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    pub fn sweep_native_epoch(ctx: Context<SweepNativeEpoch>, epoch_nr: u64) -> Result<()> {
+        sweep_native_epoch_handler(ctx, epoch_nr)
+    }
+
+    /// Moves the unclaimed rewards of an epoch into the vault of the next epoch distributing
+    /// the same mint once its claim deadline has passed, and closes the epoch for claims of the
+    /// mint. If the next epoch is not approved yet, rewards of its primary mint count toward
+    /// what its approval funds; otherwise, and for an added mint, they are credited to its
+    /// `funded_amount`.
+    /// Epochs with several mints are rolled over once per mint.
+    /// Can only be called by the `Manager` or a member granted the `Approver` role.
+    /// In multisig mode, it is called by a member of the manager multisig with the proposal
+    /// rolling the epoch over, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to roll over.
+    /// * `next_epoch_nr` - The number of the later epoch receiving the rewards.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidMintAccount` - Thrown if either epoch does not distribute the mint, or the
+    ///   next epoch mints it.
+    /// * `ClaimDeadlineNotReached` - Thrown if the claim deadline has not passed.
+    /// * `EpochShouldBeApproved` - Thrown if the epoch is not approved.
+    /// * `InvalidEpochNr` - Thrown if the next epoch does not come after the epoch.
+    /// * `EpochSwept` - Thrown if the mint of either epoch was already swept or rolled over.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal is not for this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    pub fn rollover_epoch(
+        ctx: Context<RolloverEpoch>,
+        epoch_nr: u64,
+        next_epoch_nr: u64,
+    ) -> Result<()> {
+        rollover_epoch_handler(ctx, epoch_nr, next_epoch_nr)
+    }
+
+    /// Closes an epoch whose vaults are empty, because it was fully claimed or swept,
+    /// together with its ATAs and claim bitmap shards. The rent goes back to who paid it.
+    /// No claim can be made on the epoch after it is closed.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role. In
+    /// multisig mode, it is called by a member of the manager multisig with the proposal
+    /// closing the epoch, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to close.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidEpochNr` - Thrown if the epoch is the latest approved one in cumulative mode.
+    /// * `EpochShouldBeApproved` - Thrown if the epoch is not approved.
+    /// * `EpochNotSettled` - Thrown if a mint of the epoch was neither claimed in full nor swept.
+    /// * `EpochVaultNotEmpty` - Thrown if the epoch vault still holds tokens.
+    /// * `InvalidRentPayer` - Thrown if the rent is not sent back to who paid it.
+    /// * `InvalidRemainingAccounts` - Thrown if the ATAs of the additional mints or the claim
+    ///   bitmap shards are not all passed.
+    /// * `InvalidClaimBitmapShard` - Thrown if the claim bitmap shards are not passed in order.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal is not for this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    pub fn close_epoch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseEpoch<'info>>,
+        epoch_nr: u64,
+    ) -> Result<()> {
+        close_epoch_handler(ctx, epoch_nr)
+    }
+
+    /// Closes a native SOL epoch whose vault is down to its rent-exempt minimum, because it
+    /// was fully claimed or swept, together with its claim bitmap shards. The vault is
+    /// emptied, and the rent goes back to who paid it.
+    /// No claim can be made on the epoch after it is closed.
+    /// Can only be called by the `Manager` or a member granted the `ConfigAdmin` role. In
+    /// multisig mode, it is called by a member of the manager multisig with the proposal
+    /// closing the epoch, which it approves and executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `epoch_nr` - The epoch number to close.
     ///
     /// # Errors
     ///
     /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
     /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
-    pub fn approve_epoch(ctx: Context<ApproveEpoch>, epoch_nr: u64, amount: u64) -> Result<()> {
-        approve_epoch_handler(ctx, epoch_nr, amount)
+    /// * `NotNativeEpoch` - Thrown if the epoch distributes tokens.
+    /// * `EpochShouldBeApproved` - Thrown if the epoch is not approved.
+    /// * `EpochNotSettled` - Thrown if a mint of the epoch was neither claimed in full nor swept.
+    /// * `EpochVaultNotEmpty` - Thrown if the vault holds more than its rent-exempt minimum.
+    /// * `InvalidRentPayer` - Thrown if the rent is not sent back to who paid it.
+    /// * `InvalidRemainingAccounts` - Thrown if the claim bitmap shards are not all passed.
+    /// * `InvalidClaimBitmapShard` - Thrown if the claim bitmap shards are not passed in order.
+    /// * `ProposalRequired` - Thrown in multisig mode if no proposal is passed.
+    /// * `ProposalMismatch` - Thrown if the proposal is not for this action.
+    /// * `ProposalThresholdNotReached` - Thrown if the proposal lacks approvals.
+    pub fn close_native_epoch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseNativeEpoch<'info>>,
+        epoch_nr: u64,
+    ) -> Result<()> {
+        close_native_epoch_handler(ctx, epoch_nr)
+    }
+
+    /// Closes the claim status of a claim once its epoch is swept or closed,
+    /// and sends the rent back to who paid it. Can be called by anyone.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `index` - Index of the claim in the Merkle tree.
+    ///
+    /// # Errors
+    ///
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `EpochStillClaimable` - Thrown if the epoch is neither swept nor closed.
+    /// * `InvalidRentPayer` - Thrown if the rent is not sent back to who paid it.
+    pub fn close_claim_status(ctx: Context<CloseClaimStatus>, index: u64) -> Result<()> {
+        close_claim_status_handler(ctx, index)
     }
 
     /// Sends rewards to the signer if they have an allocation in the submitted Merkle tree.
@@ -132,6 +1198,7 @@ pub mod rewards_distributor {
     /// * `index` - Index of the claim in the Merkle tree.
     /// * `amount` - Amount to be claimed by the user.
     /// * `proof` - Merkle proof for verifying the claim, which is a vector of 32-byte arrays.
+    /// * `mint_index` - Index of the mint of the epoch paid by the leaf, 0 for the primary mint.
     ///
     /// # Errors
     ///
@@ -140,17 +1207,148 @@ pub mod rewards_distributor {
     /// * `OwnerMismatch` - Provided `to` account is not the same as reciever's public key.
     /// * `DropAlreadyClaimed` - User has already claimed.
     /// * `InvalidProof` - Provided proof is invalid.
+    /// * `InvalidMintIndex` - The epoch has no mint at `mint_index`.
+    /// * `EpochSwept` - The unclaimed rewards of the epoch were swept.
+    /// * `ClaimsNotOpen` - The challenge window of the epoch is not over.
+    /// * `ClaimDeadlinePassed` - The claim deadline of the epoch has passed.
+    /// * `MaxTotalClaimExceeded` - The claim exceeds the declared total of the epoch.
+    /// * `FundedAmountExceeded` - The claim exceeds the amount funded for its added mint.
+    /// * `MaxNumNodesExceeded` - The claim exceeds the declared number of nodes of the epoch.
+    /// * `InvalidMintAuthority` - The mint authority PDA is missing for an epoch in mint mode.
+    /// * `EmissionCapExceeded` - The claim would mint past the emission cap.
     pub fn claim(
         ctx: Context<Claim>,
         index: u64,
         amount: u64,
         proof: Vec<[u8; 32]>,
+        mint_index: u8,
     ) -> Result<()> {
-        claim_handler(ctx, index, amount, proof)
+        claim_handler(ctx, index, amount, proof, mint_index)
     }
 
-    /// Pauses the program.
-    /// Can only be called by the `Manager`.
+    /// Sends lamports of a native SOL epoch to the signer if they have an allocation
+    /// in the submitted Merkle tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `index` - Index of the claim in the Merkle tree.
+    /// * `amount` - Amount of lamports to be claimed by the user.
+    /// * `proof` - Merkle proof for verifying the claim, which is a vector of 32-byte arrays.
+    ///
+    /// # Errors
+    ///
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `NotNativeEpoch` - Thrown if the epoch distributes tokens.
+    /// * `DropAlreadyClaimed` - User has already claimed.
+    /// * `InvalidProof` - Provided proof is invalid.
+    /// * `InsufficientVaultBalance` - The vault cannot pay the claim and stay rent-exempt.
+    /// * `EpochSwept` - The unclaimed rewards of the epoch were swept.
+    /// * `ClaimsNotOpen` - The challenge window of the epoch is not over.
+    /// * `ClaimDeadlinePassed` - The claim deadline of the epoch has passed.
+    /// * `MaxTotalClaimExceeded` - The claim exceeds the declared total of the epoch.
+    /// * `MaxNumNodesExceeded` - The claim exceeds the declared number of nodes of the epoch.
+    pub fn claim_native(
+        ctx: Context<ClaimNative>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        claim_native_handler(ctx, index, amount, proof)
+    }
+
+    /// Sends the signer the part of their cumulative allocation that was not paid yet.
+    /// Only available in [DistributionMode::Cumulative], against the latest approved epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `index` - Index of the claim in the Merkle tree.
+    /// * `cumulative_amount` - Lifetime amount allocated to the user.
+    /// * `proof` - Merkle proof for verifying the claim, which is a vector of 32-byte arrays.
+    ///
+    /// # Errors
+    ///
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidDistributionMode` - Thrown if the program is not in cumulative mode.
+    /// * `InvalidProof` - Provided proof is invalid.
+    /// * `NothingToClaim` - Everything allocated was already claimed.
+    /// * `MaxTotalClaimExceeded` - The claim exceeds the declared total of the epoch.
+    /// * `MaxNumNodesExceeded` - The claim exceeds the declared number of nodes of the epoch.
+    pub fn claim_cumulative(
+        ctx: Context<ClaimCumulative>,
+        index: u64,
+        cumulative_amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        claim_cumulative_handler(ctx, index, cumulative_amount, proof)
+    }
+
+    /// Sends rewards to the signer for several epochs at once.
+    /// For every entry, `remaining_accounts` must contain the [EpochAccount], its ATA and
+    /// the [ClaimStatus] PDA to create, in that order.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `entries` - The claims to make, one per epoch and Merkle tree index.
+    ///
+    /// # Errors
+    ///
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `InvalidRemainingAccounts` - Remaining accounts do not match the entries.
+    /// * `ClaimEntryFailed` - An entry failed, its position and error are the compared values:
+    ///   * `DropAlreadyClaimed` - User has already claimed the entry.
+    ///   * `InvalidProof` - The provided proof is invalid.
+    ///   * `MaxTotalClaimExceeded` - The claim exceeds the declared total of the epoch.
+    ///   * `FundedAmountExceeded` - The claim exceeds the amount funded for its added mint.
+    ///   * `MaxNumNodesExceeded` - The claim exceeds the declared number of nodes of the epoch.
+    ///   * `MintModeEpoch` - The entry is paid by minting, which only `claim` supports.
+    pub fn claim_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>,
+        entries: Vec<ClaimEntry>,
+    ) -> Result<()> {
+        claim_many_handler(ctx, entries)
+    }
+
+    /// Sends rewards to a receiver on their behalf, with a relayer paying the fees and rent.
+    /// The receiver's consent is an ed25519 signature over the claim parameters, verified by
+    /// an Ed25519 program instruction placed right before this one.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `index` - Index of the claim in the Merkle tree.
+    /// * `amount` - Amount to be claimed by the receiver.
+    /// * `proof` - Merkle proof for verifying the claim, which is a vector of 32-byte arrays.
+    /// * `expiry` - Unix timestamp after which the authorization is no longer valid.
+    /// * `mint_index` - Index of the mint of the epoch paid by the leaf, 0 for the primary mint.
+    ///
+    /// # Errors
+    ///
+    /// * `ShouldNotBePaused` - Thrown if the protocol is paused.
+    /// * `AuthorizationExpired` - The authorization is past its expiry.
+    /// * `InvalidAuthorization` - No matching ed25519 signature of the receiver was found.
+    /// * `InvalidProof` - Provided proof is invalid.
+    /// * `InvalidMintIndex` - The epoch has no mint at `mint_index`.
+    /// * `MaxTotalClaimExceeded` - The claim exceeds the declared total of the epoch.
+    /// * `FundedAmountExceeded` - The claim exceeds the amount funded for its added mint.
+    /// * `MaxNumNodesExceeded` - The claim exceeds the declared number of nodes of the epoch.
+    /// * `MintModeEpoch` - The leaf is paid by minting, which only `claim` supports.
+    pub fn claim_with_authorization(
+        ctx: Context<ClaimWithAuthorization>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        expiry: i64,
+        mint_index: u8,
+    ) -> Result<()> {
+        claim_with_authorization_handler(ctx, index, amount, proof, expiry, mint_index)
+    }
+
+    /// Pauses all the operations of the program, without expiry, including those left running
+    /// by a partial pause.
+    /// Can only be called by the `Manager`, a member granted the `Pauser` role or the guardian.
     ///
     /// # Arguments
     ///
@@ -158,13 +1356,40 @@ pub mod rewards_distributor {
     ///
     /// # Errors
     ///
-    /// * `ShouldNotBePaused` - Thrown if the protocol is already paused.
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `ShouldNotBePaused` - Thrown if all the operations are already paused.
     pub fn pause(ctx: Context<Pause>) -> Result<()> {
         pause_handler(ctx)
     }
 
-    /// Unpauses the program.
-    /// Can only be called by the `Manager`.
+    /// Pauses the operations of the `flags` bits (1 claims, 2 submissions, 4 approvals,
+    /// 8 admin) on top of those already paused.
+    /// Can only be called by the `Manager`, a member granted the `Pauser` role or the guardian.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `flags` - Pause bits to set.
+    /// * `reason` - Reason code of the pause, defined off-chain.
+    /// * `expires_at` - Unix timestamp at which the pause lapses, `0` for none.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `InvalidPauseFlags` - Thrown if the flags are empty or contain unknown bits.
+    /// * `InvalidPauseExpiry` - Thrown if the expiry is in the past.
+    pub fn pause_operations(
+        ctx: Context<PauseOperations>,
+        flags: u8,
+        reason: u16,
+        expires_at: i64,
+    ) -> Result<()> {
+        pause_operations_handler(ctx, flags, reason, expires_at)
+    }
+
+    /// Unpauses all the operations of the program.
+    /// Can only be called by the `Manager` or a member granted the `Pauser` role, through a
+    /// proposal in multisig mode.
     ///
     /// # Arguments
     ///
@@ -173,7 +1398,27 @@ pub mod rewards_distributor {
     /// # Errors
     ///
     /// * `ShouldBePaused` - Thrown if the protocol is already unpaused.
+    /// * `ProposalRequired` - Thrown in multisig mode.
     pub fn unpause(ctx: Context<UnPause>) -> Result<()> {
         unpause_handler(ctx)
     }
+
+    /// Resumes the operations of the `flags` bits.
+    /// Can only be called by the `Manager` or a member granted the `Pauser` role, and not in
+    /// multisig mode, where unpausing goes through a proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Context for the instruction.
+    /// * `flags` - Pause bits to clear.
+    ///
+    /// # Errors
+    ///
+    /// * `Unauthorized` - Provided Signer is not authorized to call this instruction.
+    /// * `InvalidPauseFlags` - Thrown if the flags are empty or contain unknown bits.
+    /// * `ShouldBePaused` - Thrown if none of the operations is paused.
+    /// * `ProposalRequired` - Thrown in multisig mode.
+    pub fn unpause_operations(ctx: Context<UnpauseOperations>, flags: u8) -> Result<()> {
+        unpause_operations_handler(ctx, flags)
+    }
 }
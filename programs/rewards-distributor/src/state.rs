@@ -1,3 +1,9 @@
+use crate::errors::ErrorCode;
+use crate::events::{
+    AgentChanged, AgentQuorumChanged, EmissionScheduleChanged, EpochQuorumReached,
+    EpochRootDiverged, ManagerMultisigChanged, NewProposedAgent, NewProposedManager, Paused,
+    ProposalApproved, ProposalExecuted, TimelockDelayChanged, TreasuryChanged,
+};
 use anchor_lang::{prelude::*, solana_program::pubkey::PUBKEY_BYTES};
 
 /// Data account to store the `Manager` of the program.
@@ -21,8 +27,769 @@ pub struct RewardsAccount {
     /// The currently approved epoch number.
     pub current_approved_epoch: u64,
 
-    /// Indicates if the program is paused.
-    pub is_paused: bool,
+    /// Paused operations, a combination of the `PAUSE_*` bits.
+    pub pause_flags: u8,
+
+    /// How the merkle leaves of the epochs are to be interpreted.
+    pub distribution_mode: DistributionMode,
+
+    /// Number of seconds an epoch can be claimed after its approval, 0 for no deadline.
+    pub claim_window: i64,
+
+    /// Owner of the token accounts receiving the unclaimed rewards of swept epochs.
+    pub treasury: Pubkey,
+
+    /// Number of seconds between the approval of an epoch and the opening of its claims,
+    /// during which the epoch can be vetoed.
+    pub challenge_window: i64,
+
+    /// Who can veto approved epochs during their challenge window, besides the `Manager`.
+    pub guardian: Pubkey,
+
+    /// Who can top up approved epochs, besides the `Manager`.
+    pub funder: Pubkey,
+
+    /// Maximum amount of tokens, in base units, claims of epochs in mint mode can mint.
+    pub emission_cap: u64,
+
+    /// Total amount of tokens, in base units, minted by claims of epochs in mint mode.
+    pub total_minted: u64,
+
+    /// Caps the amount token epochs can issue, `None` for no cap.
+    pub emission_schedule: Option<EmissionSchedule>,
+
+    /// Members of the manager multisig, empty in single-key mode.
+    #[max_len(MAX_MULTISIG_MEMBERS)]
+    pub multisig_members: Vec<Pubkey>,
+
+    /// Number of member approvals a [ManagerProposal] needs to execute, 0 in single-key mode.
+    pub multisig_threshold: u8,
+
+    /// Number of manager proposals created so far.
+    pub proposal_count: u64,
+
+    /// Agents submitting the roots of the epochs in quorum mode, empty in single-agent mode.
+    #[max_len(MAX_QUORUM_AGENTS)]
+    pub quorum_agents: Vec<Pubkey>,
+
+    /// Number of `quorum_agents` that must submit the identical root of an epoch before
+    /// it can be approved, 0 in single-agent mode.
+    pub agent_quorum: u8,
+
+    /// Number of seconds between queueing a [TimelockOperation] and executing it, 0 while
+    /// the timelock is disabled.
+    pub timelock_delay: i64,
+
+    /// Number of timelock operations queued so far.
+    pub timelock_count: u64,
+
+    /// Pubkey of the `ProposedAgent`, who becomes the agent once it accepts.
+    pub proposed_agent: Pubkey,
+
+    /// Number of seconds the current agent keeps submitting once the `proposed_agent` accepts.
+    pub proposed_agent_overlap: i64,
+
+    /// Pubkey of the agent replaced by the last accepted proposal.
+    pub previous_agent: Pubkey,
+
+    /// Unix timestamp until which the `previous_agent` can still submit epochs.
+    pub previous_agent_until: i64,
+
+    /// Reason code of the last pause, defined off-chain.
+    pub pause_reason: u16,
+
+    /// Unix timestamp at which the `pause_flags` lapse, 0 for a pause until unpaused.
+    pub pause_expires_at: i64,
+}
+
+/// Pause bit of the claims.
+pub const PAUSE_CLAIMS: u8 = 1 << 0;
+
+/// Pause bit of the submission and correction of epochs.
+pub const PAUSE_SUBMISSIONS: u8 = 1 << 1;
+
+/// Pause bit of the approval, rejection, funding and closing of epochs. Vetoes are never paused.
+pub const PAUSE_APPROVALS: u8 = 1 << 2;
+
+/// Pause bit of the configuration and authority changes.
+pub const PAUSE_ADMIN: u8 = 1 << 3;
+
+/// All the pause bits.
+pub const PAUSE_ALL: u8 = PAUSE_CLAIMS | PAUSE_SUBMISSIONS | PAUSE_APPROVALS | PAUSE_ADMIN;
+
+impl RewardsAccount {
+    /// Returns true if any of the `flags` operations is paused and the pause has not expired.
+    pub fn is_paused(&self, flags: u8) -> bool {
+        self.pause_flags & flags != 0
+            && (self.pause_expires_at == 0
+                || Clock::get().map_or(true, |clock| clock.unix_timestamp < self.pause_expires_at))
+    }
+
+    /// Returns true if `signer` can set pause bits: holders of the pauser role and the `guardian`.
+    pub fn can_pause(&self, signer: &Pubkey, role_grant: Option<&RoleGrant>) -> bool {
+        *signer == self.guardian || self.has_role(signer, Role::Pauser, role_grant)
+    }
+
+    /// Pauses the `flags` operations on top of those already paused, for `reason` and until
+    /// `expires_at` (0 for no expiry). A pause expiring later, or not at all, is kept.
+    pub fn pause(&mut self, flags: u8, reason: u16, expires_at: i64) {
+        if !self.is_paused(PAUSE_ALL) {
+            // Bits of an expired pause no longer hold.
+            self.pause_flags = 0;
+            self.pause_expires_at = expires_at;
+        } else if self.pause_expires_at != 0 {
+            self.pause_expires_at = match expires_at {
+                0 => 0,
+                _ => self.pause_expires_at.max(expires_at),
+            };
+        }
+        self.pause_flags |= flags;
+        self.pause_reason = reason;
+
+        self.emit_paused();
+    }
+
+    /// Resumes the `flags` operations.
+    pub fn unpause(&mut self, flags: u8) {
+        self.pause_flags &= !flags;
+        if self.pause_flags == 0 {
+            self.pause_reason = 0;
+            self.pause_expires_at = 0;
+        }
+
+        self.emit_paused();
+    }
+
+    fn emit_paused(&self) {
+        emit!(Paused {
+            is_paused: self.is_paused(PAUSE_ALL),
+            pause_flags: self.pause_flags,
+            reason: self.pause_reason,
+            expires_at: self.pause_expires_at,
+        });
+    }
+
+    /// Records `amount` tokens minted by a claim, failing if it exceeds the `emission_cap`.
+    pub fn record_emission(&mut self, amount: u64) -> Result<()> {
+        self.total_minted = self
+            .total_minted
+            .checked_add(amount)
+            .filter(|total| *total <= self.emission_cap)
+            .ok_or(ErrorCode::EmissionCapExceeded)?;
+
+        Ok(())
+    }
+
+    /// Checks that epoch `epoch_nr` issuing `issued_amount` tokens of `mint` stays within
+    /// the `emission_schedule`, if any.
+    pub fn assert_within_emission_schedule(
+        &self,
+        epoch_nr: u64,
+        mint: &Pubkey,
+        issued_amount: u64,
+    ) -> Result<()> {
+        if let Some(allowance) = self
+            .emission_schedule
+            .filter(|schedule| schedule.mint == *mint)
+            .and_then(|schedule| schedule.allowance(epoch_nr))
+        {
+            require!(
+                issued_amount <= allowance,
+                ErrorCode::EmissionScheduleExceeded
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if `signer` holds `role`. The `agent` submits epochs, the `funder` funds
+    /// them and the `manager` holds every other role; members hold the roles granted to them.
+    pub fn has_role(&self, signer: &Pubkey, role: Role, role_grant: Option<&RoleGrant>) -> bool {
+        // In quorum mode, only the quorum agents submit epochs.
+        if role == Role::Submitter && self.agent_quorum > 0 {
+            return self.quorum_agents.contains(signer);
+        }
+
+        let is_default_holder = match role {
+            Role::Submitter => self.is_agent(signer),
+            Role::Funder => *signer == self.manager || *signer == self.funder,
+            Role::Approver | Role::Pauser | Role::ConfigAdmin => *signer == self.manager,
+        };
+
+        is_default_holder
+            || role_grant.is_some_and(|grant| grant.role == role && grant.member == *signer)
+    }
+
+    /// Returns true if `signer` is the agent, or the previous agent during its overlap period.
+    pub fn is_agent(&self, signer: &Pubkey) -> bool {
+        *signer == self.agent
+            || (*signer == self.previous_agent
+                && Clock::get().is_ok_and(|clock| clock.unix_timestamp < self.previous_agent_until))
+    }
+
+    /// Changes the agent at once, ending the overlap period of the previous agent.
+    pub fn set_agent(&mut self, new_agent: Pubkey) {
+        self.agent = new_agent;
+        self.previous_agent_until = 0;
+        emit!(AgentChanged { new_agent });
+    }
+
+    /// Proposes `proposed_agent`, who keeps the current agent submitting for `overlap`
+    /// seconds once it accepts.
+    pub fn propose_agent(&mut self, proposed_agent: Pubkey, overlap: i64) -> Result<()> {
+        require!(overlap >= 0, ErrorCode::InvalidAgentOverlap);
+        self.proposed_agent = proposed_agent;
+        self.proposed_agent_overlap = overlap;
+        emit!(NewProposedAgent {
+            proposed_agent,
+            overlap
+        });
+
+        Ok(())
+    }
+
+    /// Changes the quorum agents and the agent quorum, both empty for a single agent.
+    pub fn set_agent_quorum(&mut self, agents: Vec<Pubkey>, quorum: u8) {
+        self.quorum_agents = agents.clone();
+        self.agent_quorum = quorum;
+        emit!(AgentQuorumChanged { agents, quorum });
+    }
+
+    /// Changes the members and threshold of the manager multisig, both empty for a single key.
+    pub fn set_multisig(&mut self, members: Vec<Pubkey>, threshold: u8) {
+        self.multisig_members = members.clone();
+        self.multisig_threshold = threshold;
+        emit!(ManagerMultisigChanged { members, threshold });
+    }
+
+    /// Returns true if enough quorum agents submitted the root of `epoch_account`, or if
+    /// there is no agent quorum.
+    pub fn has_root_quorum(&self, epoch_account: &EpochAccount) -> bool {
+        let submissions = epoch_account
+            .root_submissions
+            .iter()
+            .filter(|submission| {
+                self.quorum_agents.contains(&submission.agent) && epoch_account.matches(submission)
+            })
+            .count();
+
+        submissions >= self.agent_quorum as usize
+    }
+
+    /// Returns true if the sensitive actions of the `Manager` go through proposals of the
+    /// manager multisig.
+    pub fn is_multisig(&self) -> bool {
+        self.multisig_threshold > 0
+    }
+
+    /// Checks that `signer` can perform `action` as the `Manager`. In single-key mode it must
+    /// hold `role`. In multisig mode it must be a member approving `proposal`, which must
+    /// propose `action` and reach the threshold with this approval, and is then executed.
+    pub fn authorize_manager(
+        &self,
+        signer: &Pubkey,
+        proposal: Option<&mut ManagerProposal>,
+        role_grant: Option<&RoleGrant>,
+        role: Role,
+        action: &ManagerAction,
+    ) -> Result<()> {
+        if !self.is_multisig() {
+            require!(
+                self.has_role(signer, role, role_grant),
+                ErrorCode::Unauthorized
+            );
+            return Ok(());
+        }
+
+        let Some(proposal) = proposal else {
+            return err!(ErrorCode::ProposalRequired);
+        };
+        require!(proposal.action == *action, ErrorCode::ProposalMismatch);
+        proposal.approve(self, signer)?;
+        require!(
+            proposal.is_ready(self),
+            ErrorCode::ProposalThresholdNotReached
+        );
+
+        proposal.is_executed = true;
+        emit!(ProposalExecuted {
+            proposal_nr: proposal.proposal_nr
+        });
+
+        Ok(())
+    }
+
+    /// Returns true if the sensitive changes must be queued as a [TimelockOperation].
+    pub fn is_timelocked(&self) -> bool {
+        self.timelock_delay > 0
+    }
+
+    /// Applies the `action` of a [TimelockOperation] whose `eta` has passed.
+    pub fn execute_timelock_action(&mut self, action: &TimelockAction) -> Result<()> {
+        match action {
+            TimelockAction::ChangeAgent { new_agent } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                self.set_agent(*new_agent);
+            }
+            TimelockAction::ProposeManager { proposed_manager } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                self.proposed_manager = *proposed_manager;
+                emit!(NewProposedManager {
+                    proposed_manager: *proposed_manager
+                });
+            }
+            TimelockAction::SetTreasury { treasury } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                self.treasury = *treasury;
+                emit!(TreasuryChanged {
+                    treasury: *treasury
+                });
+            }
+            TimelockAction::SetTimelockDelay { delay } => {
+                self.timelock_delay = *delay;
+                emit!(TimelockDelayChanged { delay: *delay });
+            }
+            TimelockAction::ProposeAgent {
+                proposed_agent,
+                overlap,
+            } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                self.propose_agent(*proposed_agent, *overlap)?;
+            }
+            TimelockAction::SetEmissionSchedule { emission_schedule } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                self.emission_schedule = Some(*emission_schedule);
+                emit!(EmissionScheduleChanged {
+                    emission_schedule: *emission_schedule
+                });
+            }
+            TimelockAction::SetAgentQuorum { agents, quorum } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                self.set_agent_quorum(agents.clone(), *quorum);
+            }
+            TimelockAction::SetMultisig { members, threshold } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                self.set_multisig(members.clone(), *threshold);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Executes `proposal` if it reached the threshold. Actions moving funds or accounts,
+    /// such as epoch approvals, and timelock operations are left to their own instruction,
+    /// which needs more accounts.
+    pub fn execute_proposal(&mut self, proposal: &mut ManagerProposal) -> Result<()> {
+        if !proposal.is_ready(self) {
+            return Ok(());
+        }
+
+        match &proposal.action {
+            ManagerAction::ApproveEpoch { .. }
+            | ManagerAction::QueueTimelockOperation { .. }
+            | ManagerAction::WithdrawTreasury { .. }
+            | ManagerAction::ReleaseMintAuthority { .. }
+            | ManagerAction::SweepEpoch { .. }
+            | ManagerAction::RolloverEpoch { .. }
+            | ManagerAction::CloseEpoch { .. }
+            | ManagerAction::GrantRole { .. }
+            | ManagerAction::RevokeRole { .. } => return Ok(()),
+            ManagerAction::ChangeAgent { new_agent } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                require!(!self.is_timelocked(), ErrorCode::TimelockRequired);
+                self.set_agent(*new_agent);
+            }
+            ManagerAction::ProposeManager { proposed_manager } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                require!(!self.is_timelocked(), ErrorCode::TimelockRequired);
+                self.proposed_manager = *proposed_manager;
+                emit!(NewProposedManager {
+                    proposed_manager: *proposed_manager
+                });
+            }
+            ManagerAction::Unpause => {
+                require!(self.is_paused(PAUSE_ALL), ErrorCode::ShouldBePaused);
+                self.unpause(PAUSE_ALL);
+            }
+            ManagerAction::SetAgentQuorum { agents, quorum } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                require!(!self.is_timelocked(), ErrorCode::TimelockRequired);
+                self.set_agent_quorum(agents.clone(), *quorum);
+            }
+            ManagerAction::ProposeAgent {
+                proposed_agent,
+                overlap,
+            } => {
+                require!(!self.is_paused(PAUSE_ADMIN), ErrorCode::ShouldNotBePaused);
+                require!(!self.is_timelocked(), ErrorCode::TimelockRequired);
+                self.propose_agent(*proposed_agent, *overlap)?;
+            }
+            ManagerAction::SetMultisig { members, threshold } => {
+                require!(!self.is_timelocked(), ErrorCode::TimelockRequired);
+                self.set_multisig(members.clone(), *threshold);
+            }
+        }
+
+        proposal.is_executed = true;
+        emit!(ProposalExecuted {
+            proposal_nr: proposal.proposal_nr
+        });
+
+        Ok(())
+    }
+}
+
+/// Maximum number of members of the manager multisig.
+pub const MAX_MULTISIG_MEMBERS: usize = 10;
+
+/// Maximum number of quorum agents.
+pub const MAX_QUORUM_AGENTS: usize = 8;
+
+/// Returns true if `members` are at most `max_members`, without duplicates, and `threshold`
+/// is between 1 and their number, or both are empty.
+fn is_valid_threshold(members: &[Pubkey], threshold: u8, max_members: usize) -> bool {
+    let is_unique = members
+        .iter()
+        .enumerate()
+        .all(|(i, member)| !members[..i].contains(member));
+
+    members.len() <= max_members
+        && is_unique
+        && threshold as usize <= members.len()
+        && (threshold == 0) == members.is_empty()
+}
+
+/// Checks that `members` and `threshold` are a valid manager multisig, both empty
+/// switching back to single-key mode.
+pub fn assert_valid_multisig(members: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        is_valid_threshold(members, threshold, MAX_MULTISIG_MEMBERS),
+        ErrorCode::InvalidMultisig
+    );
+
+    Ok(())
+}
+
+/// Checks that `agents` and `quorum` are a valid agent quorum, both empty switching back
+/// to single-agent mode.
+pub fn assert_valid_agent_quorum(agents: &[Pubkey], quorum: u8) -> Result<()> {
+    require!(
+        is_valid_threshold(agents, quorum, MAX_QUORUM_AGENTS),
+        ErrorCode::InvalidAgentQuorum
+    );
+
+    Ok(())
+}
+
+/// A sensitive action of the `Manager`, executed through a [ManagerProposal] in multisig mode.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ManagerAction {
+    /// Approves an epoch, with the arguments of its approve instruction, the instruction
+    /// and the account funding the epoch.
+    ApproveEpoch {
+        epoch_nr: u64,
+        amount: u64,
+        expected_root: [u8; 32],
+        expected_correction_count: u64,
+        kind: ApprovalKind,
+        funding_source: FundingSource,
+    },
+
+    /// Changes the agent.
+    ChangeAgent { new_agent: Pubkey },
+
+    /// Proposes a new manager.
+    ProposeManager { proposed_manager: Pubkey },
+
+    /// Unpauses the program.
+    Unpause,
+
+    /// Changes the quorum agents and the agent quorum.
+    SetAgentQuorum {
+        #[max_len(MAX_QUORUM_AGENTS)]
+        agents: Vec<Pubkey>,
+        quorum: u8,
+    },
+
+    /// Changes the members and threshold of the manager multisig.
+    SetMultisig {
+        #[max_len(MAX_MULTISIG_MEMBERS)]
+        members: Vec<Pubkey>,
+        threshold: u8,
+    },
+
+    /// Queues a timelock operation, with the arguments of its queue instruction.
+    QueueTimelockOperation { action: TimelockAction },
+
+    /// Proposes a new agent, with the overlap period of the current agent.
+    ProposeAgent {
+        proposed_agent: Pubkey,
+        overlap: i64,
+    },
+
+    /// Withdraws `amount` tokens of `mint` from its treasury vault to the token account `to`.
+    WithdrawTreasury {
+        mint: Pubkey,
+        to: Pubkey,
+        amount: u64,
+    },
+
+    /// Hands the mint authority of `mint` from the program to `new_authority`.
+    ReleaseMintAuthority { mint: Pubkey, new_authority: Pubkey },
+
+    /// Sweeps the unclaimed rewards of `mint` of an epoch, the default pubkey for native SOL.
+    SweepEpoch { epoch_nr: u64, mint: Pubkey },
+
+    /// Rolls the unclaimed rewards of `mint` of an epoch over into the epoch `next_epoch_nr`.
+    RolloverEpoch {
+        epoch_nr: u64,
+        next_epoch_nr: u64,
+        mint: Pubkey,
+    },
+
+    /// Closes an epoch.
+    CloseEpoch { epoch_nr: u64 },
+
+    /// Grants `role` to `member`.
+    GrantRole { role: Role, member: Pubkey },
+
+    /// Revokes `role` from `member`.
+    RevokeRole { role: Role, member: Pubkey },
+}
+
+/// Instruction approving an epoch, which determines how it is funded.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ApprovalKind {
+    /// `approve_epoch`, funding the epoch ATA.
+    Token,
+
+    /// `approve_native_epoch`, funding the lamport vault.
+    Native,
+
+    /// `approve_mint_epoch`, minting the claims.
+    Mint,
+}
+
+/// A sensitive change of the configuration, queued as a [TimelockOperation].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum TimelockAction {
+    /// Changes the agent.
+    ChangeAgent { new_agent: Pubkey },
+
+    /// Proposes a new manager, who can accept once the operation is executed.
+    ProposeManager { proposed_manager: Pubkey },
+
+    /// Changes the treasury receiving the swept rewards.
+    SetTreasury { treasury: Pubkey },
+
+    /// Changes the timelock delay, 0 disabling the timelock.
+    SetTimelockDelay { delay: i64 },
+
+    /// Proposes a new agent, with the overlap period of the current agent.
+    ProposeAgent {
+        proposed_agent: Pubkey,
+        overlap: i64,
+    },
+
+    /// Changes the emission schedule capping what token epochs issue.
+    SetEmissionSchedule { emission_schedule: EmissionSchedule },
+
+    /// Changes the quorum agents and the agent quorum.
+    SetAgentQuorum {
+        #[max_len(MAX_QUORUM_AGENTS)]
+        agents: Vec<Pubkey>,
+        quorum: u8,
+    },
+
+    /// Changes the members and threshold of the manager multisig.
+    SetMultisig {
+        #[max_len(MAX_MULTISIG_MEMBERS)]
+        members: Vec<Pubkey>,
+        threshold: u8,
+    },
+}
+
+/// A [TimelockAction] queued by the `Manager`, executable by anyone from its `eta` until
+/// it is cancelled.
+#[account]
+#[derive(InitSpace)]
+pub struct TimelockOperation {
+    /// The [RewardsAccount] the operation belongs to.
+    pub rewards_account: Pubkey,
+
+    /// Number of the operation (starts at 1).
+    pub operation_nr: u64,
+
+    /// The queued action.
+    pub action: TimelockAction,
+
+    /// Unix timestamp from which the operation can be executed.
+    pub eta: i64,
+
+    /// Indicates if the operation was executed.
+    pub is_executed: bool,
+
+    /// Indicates if the operation was cancelled.
+    pub is_cancelled: bool,
+}
+
+impl TimelockOperation {
+    /// Returns true if the operation was neither executed nor cancelled.
+    pub fn is_pending(&self) -> bool {
+        !self.is_executed && !self.is_cancelled
+    }
+}
+
+/// A [ManagerAction] proposed by a member of the manager multisig, executed once
+/// `multisig_threshold` members approved it.
+#[account]
+#[derive(InitSpace)]
+pub struct ManagerProposal {
+    /// The [RewardsAccount] the proposal belongs to.
+    pub rewards_account: Pubkey,
+
+    /// Number of the proposal (starts at 1).
+    pub proposal_nr: u64,
+
+    /// Member who created the proposal.
+    pub proposer: Pubkey,
+
+    /// The proposed action.
+    pub action: ManagerAction,
+
+    /// Members who approved the proposal.
+    #[max_len(MAX_MULTISIG_MEMBERS)]
+    pub approvers: Vec<Pubkey>,
+
+    /// Indicates if the proposal was executed.
+    pub is_executed: bool,
+}
+
+impl ManagerProposal {
+    /// Records the approval of `member`, who must be a member of the manager multisig.
+    pub fn approve(&mut self, rewards_account: &RewardsAccount, member: &Pubkey) -> Result<()> {
+        let members = &rewards_account.multisig_members;
+        require!(members.contains(member), ErrorCode::Unauthorized);
+        require!(!self.is_executed, ErrorCode::ProposalAlreadyExecuted);
+
+        // Approvals of former members no longer count.
+        self.approvers.retain(|approver| members.contains(approver));
+        if !self.approvers.contains(member) {
+            self.approvers.push(*member);
+        }
+
+        emit!(ProposalApproved {
+            proposal_nr: self.proposal_nr,
+            member: *member,
+            approvals: self.approvers.len() as u8,
+        });
+
+        Ok(())
+    }
+
+    /// Returns true if enough members of the manager multisig approved the proposal.
+    pub fn is_ready(&self, rewards_account: &RewardsAccount) -> bool {
+        let approvals = self
+            .approvers
+            .iter()
+            .filter(|approver| rewards_account.multisig_members.contains(approver))
+            .count();
+
+        rewards_account.is_multisig() && approvals >= rewards_account.multisig_threshold as usize
+    }
+}
+
+/// A role that can be granted to members besides its default holder.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    /// Submits and corrects epochs, by default the `agent`.
+    Submitter,
+
+    /// Approves, rejects, sweeps and rolls over epochs, by default the `manager`.
+    Approver,
+
+    /// Pauses and unpauses the program, by default the `manager`.
+    Pauser,
+
+    /// Funds epochs, by default the `manager` and the `funder`.
+    Funder,
+
+    /// Sets the configuration of the program, by default the `manager`.
+    ConfigAdmin,
+}
+
+/// Grant of a [Role] to a member, derived from `["RoleGrant", rewards_account, role, member]`.
+#[account]
+#[derive(InitSpace)]
+pub struct RoleGrant {
+    /// The [RewardsAccount] the grant belongs to.
+    pub rewards_account: Pubkey,
+
+    /// The granted role.
+    pub role: Role,
+
+    /// Member holding the role.
+    pub member: Pubkey,
+}
+
+/// Basis points in one, for the decay of an [EmissionSchedule].
+pub const BPS: u64 = 10_000;
+
+/// Fixed-point scale of the decay factor of an [EmissionSchedule], 12 decimals.
+const DECAY_SCALE: u128 = 1_000_000_000_000;
+
+/// Maximum amount token epochs of `mint` can issue per epoch, starting at `start_epoch` with
+/// `initial_amount` and decaying by `decay_bps` every `decay_interval` epochs.
+/// A halving every N epochs is `decay_bps = 5_000` and `decay_interval = N`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct EmissionSchedule {
+    /// [Mint] issued by the capped epochs, epochs of other mints are not capped.
+    pub mint: Pubkey,
+
+    /// First epoch capped by the schedule, earlier epochs are not capped.
+    pub start_epoch: u64,
+
+    /// Allowance, in base units, of the epochs before the first decay.
+    pub initial_amount: u64,
+
+    /// Number of epochs between two decays, 0 for a constant allowance.
+    pub decay_interval: u64,
+
+    /// Share of the allowance, in basis points, kept at each decay.
+    pub decay_bps: u64,
+}
+
+impl EmissionSchedule {
+    /// Returns the allowance of `epoch_nr`, `None` if the schedule does not cap it yet.
+    /// The decay factor is compounded with 12 decimals, rounding down.
+    pub fn allowance(&self, epoch_nr: u64) -> Option<u64> {
+        let elapsed = epoch_nr.checked_sub(self.start_epoch)?;
+        let mut decays = elapsed.checked_div(self.decay_interval).unwrap_or(0);
+
+        let mut factor = DECAY_SCALE;
+        let mut base = self.decay_bps as u128 * DECAY_SCALE / BPS as u128;
+        while decays > 0 && factor > 0 {
+            if decays & 1 == 1 {
+                factor = factor * base / DECAY_SCALE;
+            }
+            base = base * base / DECAY_SCALE;
+            decays >>= 1;
+        }
+
+        Some((self.initial_amount as u128 * factor / DECAY_SCALE) as u64)
+    }
+}
+
+/// How the amount of a merkle leaf is interpreted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub enum DistributionMode {
+    /// Each leaf holds the amount earned during its epoch, claimed once per epoch.
+    #[default]
+    PerEpoch,
+
+    /// Each leaf holds the lifetime cumulative amount of the receiver,
+    /// claimed against the latest approved epoch.
+    Cumulative,
 }
 
 #[account]
@@ -39,10 +806,13 @@ pub struct ClaimStatus {
 
     /// Amount of tokens claimed.
     pub amount: u64,
+
+    /// Who paid the rent of the account, refunded when it is closed.
+    pub payer: Pubkey,
 }
 
 impl ClaimStatus {
-    pub const LEN: usize = 1 + PUBKEY_BYTES + 8 + 8;
+    pub const LEN: usize = 1 + PUBKEY_BYTES + 8 + 8 + PUBKEY_BYTES;
 }
 
 /// State for the epoch account.
@@ -61,7 +831,7 @@ pub struct EpochAccount {
     /// Bump seed.
     pub bump: u8,
 
-    /// [Mint] of the token to be distributed.
+    /// [Mint] of the token to be distributed, the default pubkey for native SOL epochs.
     pub mint: Pubkey,
 
     /// Total amount of tokens that have been claimed.
@@ -69,4 +839,479 @@ pub struct EpochAccount {
 
     /// Number of nodes that have been claimed.
     pub num_nodes_claimed: u64,
+
+    /// Total amount, in base units, the merkle tree pays in the primary `mint`, as declared by the agent.
+    pub max_total_claim: u64,
+
+    /// Number of leaves of the merkle tree, as declared by the agent.
+    pub max_num_nodes: u64,
+
+    /// Total amount, in base units, received by the vault of the primary `mint`,
+    /// when approving the epoch and topping it up afterwards.
+    pub funded_amount: u64,
+
+    /// Amount, in base units, of the primary `mint` rolled over from the previous epoch
+    /// before approval, counted toward the declared total when approving.
+    pub rolled_over_amount: u64,
+
+    /// Amount, in base units, of the primary `mint` carried over from the previous epoch
+    /// at approval in cumulative mode, which goes back to it if the epoch is vetoed.
+    pub carried_over: u64,
+
+    /// Number of times the epoch was corrected, which the manager commits to when approving.
+    pub correction_count: u64,
+
+    /// Indicates if the manager rejected the epoch, until the agent resubmits it.
+    pub is_rejected: bool,
+
+    /// Reason code of the last rejection.
+    pub rejection_reason: u16,
+
+    /// Number of times the manager rejected the epoch.
+    pub rejection_count: u64,
+
+    /// Unix timestamp from which the epoch can be claimed, once its challenge window is over.
+    pub claims_open_at: i64,
+
+    /// Number of [ClaimBitmap] shards allocated for the epoch.
+    /// When non-zero, claims are tracked in the bitmap instead of [ClaimStatus] accounts.
+    pub claim_bitmap_shards: u64,
+
+    /// Unix timestamp after which the epoch can no longer be claimed, 0 for no deadline.
+    pub claim_deadline: i64,
+
+    /// Indicates if the unclaimed rewards were swept to the treasury.
+    pub is_swept: bool,
+
+    /// Agent who paid the rent of the epoch account, refunded when it is closed.
+    pub creator: Pubkey,
+
+    /// Manager who paid the rent of the epoch ATA, refunded when it is closed.
+    pub approver: Pubkey,
+
+    /// Where the funds of the epoch came from at approval, and go back to if it is vetoed.
+    pub funding_source: FundingSource,
+
+    /// Indicates if the epoch distributes native SOL from its lamport vault instead of tokens.
+    pub is_native: bool,
+
+    /// Indicates if claims of the primary `mint` are minted by the program instead of
+    /// being paid from the epoch ATA.
+    pub is_mint_mode: bool,
+
+    /// Amount, in base units, of the primary `mint` newly issued by the epoch, capped by
+    /// the emission schedule: funded at approval on top of the carried over balance,
+    /// mintable in mint mode, and topped up afterwards.
+    pub issued_amount: u64,
+
+    /// Mints distributed on top of the primary `mint`, selected by leaves with a
+    /// mint index of 1 and above.
+    #[max_len(MAX_BONUS_MINTS)]
+    pub bonus_mints: Vec<EpochMint>,
+
+    /// Top-ups of the primary `mint` received after approval, per funder, refunded if the
+    /// epoch is vetoed.
+    #[max_len(MAX_EPOCH_TOP_UPS)]
+    pub top_ups: Vec<EpochTopUp>,
+
+    /// Latest root submission of each quorum agent since the epoch was added or last corrected.
+    #[max_len(MAX_QUORUM_AGENTS)]
+    pub root_submissions: Vec<RootSubmission>,
+
+    /// Number of root submissions that diverged from the epoch.
+    pub divergence_count: u64,
+
+    /// Hash of the distribution file listing the leaves of the merkle tree.
+    pub distribution_hash: [u8; 32],
+
+    /// Location of the distribution file, e.g. a content-addressed URI.
+    /// The account is sized to the length of the URI.
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub metadata_uri: String,
+}
+
+/// Maximum number of mints an epoch can distribute on top of its primary mint.
+pub const MAX_BONUS_MINTS: usize = 4;
+
+/// Maximum number of funders who can top up an epoch.
+pub const MAX_EPOCH_TOP_UPS: usize = 4;
+
+/// Maximum length, in bytes, of the metadata URI of an epoch.
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+/// A mint distributed by an epoch on top of its primary mint, from its own epoch ATA.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct EpochMint {
+    /// [Mint] of the token to be distributed.
+    pub mint: Pubkey,
+
+    /// Total amount of tokens received by the epoch ATA.
+    pub funded_amount: u64,
+
+    /// Total amount of tokens that have been claimed.
+    pub total_amount_claimed: u64,
+
+    /// Indicates if the unclaimed tokens were swept to the treasury or rolled over.
+    pub is_swept: bool,
+}
+
+/// Account funding an epoch at approval.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub enum FundingSource {
+    /// The `approver`, from their token account or in lamports for native SOL epochs.
+    #[default]
+    Approver,
+
+    /// The treasury vault of the primary mint.
+    TreasuryVault,
+}
+
+/// Tokens of the primary mint sent to an approved epoch by a funder.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct EpochTopUp {
+    /// Who sent the tokens, and receives them back if the epoch is vetoed.
+    pub funder: Pubkey,
+
+    /// Total amount of tokens received by the epoch ATA from the funder.
+    pub amount: u64,
+}
+
+/// Root and totals of an epoch as submitted by a quorum agent.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct RootSubmission {
+    /// The submitting agent.
+    pub agent: Pubkey,
+
+    /// Submitted merkle tree root.
+    pub root: [u8; 32],
+
+    /// Submitted total amount of the merkle tree.
+    pub max_total_claim: u64,
+
+    /// Submitted number of leaves of the merkle tree.
+    pub max_num_nodes: u64,
+
+    /// Submitted mint of the epoch, the default pubkey for native SOL.
+    pub mint: Pubkey,
+}
+
+impl EpochAccount {
+    /// Returns the size of an epoch account, discriminator included, storing `metadata_uri`
+    /// and as many `bonus_mints`, `top_ups` and `root_submissions` as given.
+    pub fn space(
+        metadata_uri: &str,
+        bonus_mints: usize,
+        top_ups: usize,
+        root_submissions: usize,
+    ) -> usize {
+        8 + Self::INIT_SPACE
+            - MAX_METADATA_URI_LEN
+            - MAX_BONUS_MINTS * EpochMint::INIT_SPACE
+            - MAX_EPOCH_TOP_UPS * EpochTopUp::INIT_SPACE
+            - MAX_QUORUM_AGENTS * RootSubmission::INIT_SPACE
+            + metadata_uri.len()
+            + bonus_mints * EpochMint::INIT_SPACE
+            + top_ups * EpochTopUp::INIT_SPACE
+            + root_submissions * RootSubmission::INIT_SPACE
+    }
+
+    /// Returns the size of the epoch account once a mint is added to it.
+    pub fn space_after_bonus_mint(&self) -> usize {
+        Self::space(
+            &self.metadata_uri,
+            self.bonus_mints.len() + 1,
+            self.top_ups.len(),
+            self.root_submissions.len(),
+        )
+    }
+
+    /// Returns the size of the epoch account once `funder` tops it up.
+    pub fn space_after_top_up(&self, funder: &Pubkey) -> usize {
+        let is_new_funder = !self.top_ups.iter().any(|top_up| top_up.funder == *funder);
+        Self::space(
+            &self.metadata_uri,
+            self.bonus_mints.len(),
+            self.top_ups.len() + usize::from(is_new_funder),
+            self.root_submissions.len(),
+        )
+    }
+
+    /// Returns the size of the epoch account once `agent` submits a root to it.
+    pub fn space_after_root_submission(
+        &self,
+        rewards_account: &RewardsAccount,
+        agent: &Pubkey,
+    ) -> usize {
+        let root_submissions = self
+            .root_submissions
+            .iter()
+            .filter(|previous| Self::keeps_submission(rewards_account, agent, previous))
+            .count();
+        Self::space(
+            &self.metadata_uri,
+            self.bonus_mints.len(),
+            self.top_ups.len(),
+            root_submissions + 1,
+        )
+    }
+
+    /// Returns the size of the epoch account once `agent` corrects it with `metadata_uri`.
+    /// A correction that is only a root submission keeps the current URI.
+    pub fn space_after_correction(
+        &self,
+        rewards_account: &RewardsAccount,
+        agent: &Pubkey,
+        metadata_uri: &str,
+    ) -> usize {
+        if !self.applies_correction(rewards_account) {
+            return self.space_after_root_submission(rewards_account, agent);
+        }
+
+        // The correction clears the root submissions, and submits its own in quorum mode.
+        Self::space(
+            metadata_uri,
+            self.bonus_mints.len(),
+            self.top_ups.len(),
+            usize::from(rewards_account.agent_quorum > 0),
+        )
+    }
+
+    /// Returns true if the root `submission` still counts once `agent` submits: its previous
+    /// submission is replaced and those of former quorum agents no longer count.
+    fn keeps_submission(
+        rewards_account: &RewardsAccount,
+        agent: &Pubkey,
+        submission: &RootSubmission,
+    ) -> bool {
+        submission.agent != *agent && rewards_account.quorum_agents.contains(&submission.agent)
+    }
+
+    /// Returns true if `submission` matches the root, totals and mint of the epoch.
+    pub fn matches(&self, submission: &RootSubmission) -> bool {
+        submission.root == self.hash
+            && submission.max_total_claim == self.max_total_claim
+            && submission.max_num_nodes == self.max_num_nodes
+            && submission.mint == self.mint
+    }
+
+    /// Returns true if a correction changes the epoch. In quorum mode, a quorum agent cannot
+    /// change an epoch the others submitted on its own: its correction only counts as its
+    /// root submission until the epoch is rejected.
+    pub fn applies_correction(&self, rewards_account: &RewardsAccount) -> bool {
+        rewards_account.agent_quorum == 0 || self.is_rejected
+    }
+
+    /// Records the root submission of a quorum agent, replacing its previous one, and emits
+    /// an event if it diverges from the epoch or completes the agent quorum.
+    pub fn submit_root(&mut self, rewards_account: &RewardsAccount, submission: RootSubmission) {
+        let had_quorum = rewards_account.has_root_quorum(self);
+
+        // Submissions of former quorum agents no longer count.
+        self.root_submissions.retain(|previous| {
+            Self::keeps_submission(rewards_account, &submission.agent, previous)
+        });
+        self.root_submissions.push(submission);
+
+        if !self.matches(&submission) {
+            self.divergence_count += 1;
+            emit!(EpochRootDiverged {
+                epoch_nr: self.epoch_nr,
+                agent: submission.agent,
+                root: submission.root,
+                expected_root: self.hash,
+                mint: submission.mint,
+                expected_mint: self.mint,
+            });
+        } else if !had_quorum && rewards_account.has_root_quorum(self) {
+            emit!(EpochQuorumReached {
+                epoch_nr: self.epoch_nr,
+                root: self.hash,
+            });
+        }
+    }
+
+    /// Returns the mint distributed for `mint_index`, 0 being the primary `mint`.
+    pub fn mint_at(&self, mint_index: u8) -> Result<Pubkey> {
+        match mint_index {
+            0 => Ok(self.mint),
+            _ => self
+                .bonus_mints
+                .get(mint_index as usize - 1)
+                .map(|bonus_mint| bonus_mint.mint)
+                .ok_or_else(|| error!(ErrorCode::InvalidMintIndex)),
+        }
+    }
+
+    /// Returns the mint index of `mint`, if the epoch distributes it.
+    pub fn mint_index_of(&self, mint: &Pubkey) -> Option<u8> {
+        if *mint == self.mint {
+            return Some(0);
+        }
+
+        self.bonus_mints
+            .iter()
+            .position(|bonus_mint| bonus_mint.mint == *mint)
+            .map(|position| position as u8 + 1)
+    }
+
+    /// Returns whether leaves of `mint_index` are paid by minting tokens.
+    pub fn is_minted(&self, mint_index: u8) -> bool {
+        self.is_mint_mode && mint_index == 0
+    }
+
+    /// Records the claim of a leaf paying `amount` tokens of `mint_index`,
+    /// failing if it exceeds the totals declared for the epoch.
+    pub fn record_claim(&mut self, mint_index: u8, amount: u64) -> Result<()> {
+        match mint_index {
+            0 => {
+                self.total_amount_claimed = self
+                    .total_amount_claimed
+                    .checked_add(amount)
+                    .filter(|total| *total <= self.max_total_claim)
+                    .ok_or(ErrorCode::MaxTotalClaimExceeded)?
+            }
+            _ => {
+                // Added mints have no declared total, their claims are capped by their funding.
+                let bonus_mint = self
+                    .bonus_mints
+                    .get_mut(mint_index as usize - 1)
+                    .ok_or_else(|| error!(ErrorCode::InvalidMintIndex))?;
+                bonus_mint.total_amount_claimed = bonus_mint
+                    .total_amount_claimed
+                    .checked_add(amount)
+                    .filter(|total| *total <= bonus_mint.funded_amount)
+                    .ok_or(ErrorCode::FundedAmountExceeded)?
+            }
+        }
+
+        require!(
+            self.num_nodes_claimed < self.max_num_nodes,
+            ErrorCode::MaxNumNodesExceeded
+        );
+        self.num_nodes_claimed += 1;
+
+        Ok(())
+    }
+
+    /// Marks the unclaimed tokens of `mint_index` as swept or rolled over, failing if they
+    /// already were. The flag of the primary `mint` also closes the epoch for claims.
+    pub fn mark_swept(&mut self, mint_index: u8) -> Result<()> {
+        let is_swept = match mint_index {
+            0 => &mut self.is_swept,
+            _ => {
+                &mut self
+                    .bonus_mints
+                    .get_mut(mint_index as usize - 1)
+                    .ok_or_else(|| error!(ErrorCode::InvalidMintIndex))?
+                    .is_swept
+            }
+        };
+        require!(!*is_swept, ErrorCode::EpochSwept);
+        *is_swept = true;
+
+        Ok(())
+    }
+
+    /// Returns true if nothing is left to claim on the epoch: every mint was either
+    /// claimed in full, or swept or rolled over.
+    pub fn is_settled(&self) -> bool {
+        (self.is_swept || self.total_amount_claimed == self.max_total_claim)
+            && self.bonus_mints.iter().all(|bonus_mint| {
+                bonus_mint.is_swept || bonus_mint.total_amount_claimed == bonus_mint.funded_amount
+            })
+    }
+
+    /// Schedules the claims of an epoch approved at `now`: they open at the end of the
+    /// `challenge_window` and, if there is a `claim_window`, close at the end of it.
+    pub fn schedule_claims(&mut self, now: i64, challenge_window: i64, claim_window: i64) {
+        self.claims_open_at = now + challenge_window;
+        if claim_window > 0 {
+            self.claim_deadline = self.claims_open_at + claim_window;
+        }
+    }
+
+    /// Returns true if the epoch is approved and still in its challenge window at `now`.
+    pub fn is_challengeable(&self, now: i64) -> bool {
+        self.is_approved && now < self.claims_open_at
+    }
+
+    /// Checks that the epoch can be claimed at `now`.
+    pub fn assert_claimable(&self, now: i64) -> Result<()> {
+        require!(self.is_approved, ErrorCode::EpochShouldBeApproved);
+        require!(now >= self.claims_open_at, ErrorCode::ClaimsNotOpen);
+        require!(!self.is_swept, ErrorCode::EpochSwept);
+        require!(!self.is_past_deadline(now), ErrorCode::ClaimDeadlinePassed);
+
+        Ok(())
+    }
+
+    /// Returns true if the epoch has a claim deadline and it has passed at `now`.
+    pub fn is_past_deadline(&self, now: i64) -> bool {
+        self.claim_deadline != 0 && now > self.claim_deadline
+    }
+}
+
+/// Amount already paid to a receiver in [DistributionMode::Cumulative].
+#[account]
+#[derive(InitSpace)]
+pub struct UserClaimState {
+    /// Receiver of the rewards.
+    pub receiver: Pubkey,
+
+    /// Lifetime amount of tokens already claimed.
+    pub claimed_amount: u64,
+
+    /// Epoch number of the last claim.
+    pub last_claimed_epoch: u64,
+}
+
+/// Number of bytes of claim bits stored in each [ClaimBitmap] shard.
+pub const CLAIM_BITMAP_BYTES: usize = 4096;
+
+/// Number of merkle leaves tracked by each [ClaimBitmap] shard.
+pub const LEAVES_PER_CLAIM_BITMAP: u64 = (CLAIM_BITMAP_BYTES * 8) as u64;
+
+/// Claimed flags of a range of merkle leaves of an epoch, one bit per leaf.
+/// Shard `n` tracks the leaves `n * LEAVES_PER_CLAIM_BITMAP..(n + 1) * LEAVES_PER_CLAIM_BITMAP`.
+#[account(zero_copy)]
+pub struct ClaimBitmap {
+    /// The [EpochAccount] the shard belongs to.
+    pub epoch: Pubkey,
+
+    /// Index of the shard.
+    pub shard: u64,
+
+    /// Number of leaves of the shard that have been claimed.
+    pub num_claimed: u64,
+
+    /// Who paid the rent of the shard, refunded when the epoch is closed.
+    pub payer: Pubkey,
+
+    /// One bit per leaf, set once the leaf is claimed.
+    pub bits: [u8; CLAIM_BITMAP_BYTES],
+}
+
+impl ClaimBitmap {
+    /// Returns the shard tracking the leaf at `index`.
+    pub fn shard_of(index: u64) -> u64 {
+        index / LEAVES_PER_CLAIM_BITMAP
+    }
+
+    /// Marks the leaf at `index` as claimed, failing if it already was.
+    pub fn set_claimed(&mut self, index: u64) -> Result<()> {
+        require!(
+            Self::shard_of(index) == self.shard,
+            ErrorCode::InvalidClaimTracking
+        );
+
+        let bit = (index % LEAVES_PER_CLAIM_BITMAP) as usize;
+        let mask = 1u8 << (bit % 8);
+        let byte = &mut self.bits[bit / 8];
+        require!(*byte & mask == 0, ErrorCode::DropAlreadyClaimed);
+
+        *byte |= mask;
+        self.num_claimed += 1;
+
+        Ok(())
+    }
 }
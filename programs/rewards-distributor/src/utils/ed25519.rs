@@ -0,0 +1,63 @@
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction, pubkey::Pubkey};
+
+/// Size of the header of an Ed25519 program instruction (`num_signatures` + padding).
+const HEADER_LEN: usize = 2;
+
+/// Size of a single `Ed25519SignatureOffsets` entry.
+const OFFSETS_LEN: usize = 14;
+
+/// Size of an ed25519 signature.
+const SIGNATURE_LEN: usize = 64;
+
+/// Instruction index used by the Ed25519 program to reference its own data.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Returns true if `ix` is an Ed25519 program instruction verifying exactly one
+/// signature of `signer` over `message`, with the public key, signature and
+/// message all stored inside the instruction itself.
+///
+/// The Ed25519 program fails the whole transaction when a signature is invalid,
+/// so seeing the instruction in the same transaction proves the signature.
+pub fn verify(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> bool {
+    if ix.program_id != ed25519_program::ID || !ix.accounts.is_empty() {
+        return false;
+    }
+
+    let data = &ix.data;
+    if data.len() < HEADER_LEN + OFFSETS_LEN || data[0] != 1 {
+        return false;
+    }
+
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let offsets = HEADER_LEN;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_instruction_index = read_u16(offsets + 2);
+    let public_key_offset = read_u16(offsets + 4) as usize;
+    let public_key_instruction_index = read_u16(offsets + 6);
+    let message_data_offset = read_u16(offsets + 8) as usize;
+    let message_data_size = read_u16(offsets + 10) as usize;
+    let message_instruction_index = read_u16(offsets + 12);
+
+    // Everything has to live in the Ed25519 instruction itself, otherwise the
+    // verified bytes could come from an instruction we never look at.
+    if signature_instruction_index != CURRENT_INSTRUCTION
+        || public_key_instruction_index != CURRENT_INSTRUCTION
+        || message_instruction_index != CURRENT_INSTRUCTION
+    {
+        return false;
+    }
+
+    if signature_offset + SIGNATURE_LEN > data.len() {
+        return false;
+    }
+
+    match (
+        data.get(public_key_offset..public_key_offset + 32),
+        data.get(message_data_offset..message_data_offset + message_data_size),
+    ) {
+        (Some(public_key), Some(signed_message)) => {
+            public_key == signer.as_ref() && signed_message == message
+        }
+        _ => false,
+    }
+}
@@ -1,3 +1,5 @@
+use anchor_lang::prelude::Pubkey;
+
 /// Returns true if a `leaf` can be proved to be a part of a Merkle tree
 /// defined by `root`. For this, a `proof` must be provided, containing
 /// sibling hashes on the branch from the leaf to the root of the tree. Each
@@ -18,3 +20,20 @@ pub fn verify(proof: Vec<[u8; 32]>, root: [u8; 32], leaf: [u8; 32]) -> bool {
     // Check if the computed hash (root) is equal to the provided root
     computed_hash == root
 }
+
+/// Returns the leaf of a claim in the rewards Merkle tree,
+/// `keccak256(index || claimant || amount)` with little-endian integers for the
+/// primary mint of the epoch, and `keccak256(index || claimant || amount || mint_index)`
+/// for the other mints.
+pub fn claim_leaf(index: u64, claimant: &Pubkey, amount: u64, mint_index: u8) -> [u8; 32] {
+    let index = index.to_le_bytes();
+    let amount = amount.to_le_bytes();
+    let claimant = claimant.to_bytes();
+    match mint_index {
+        0 => anchor_lang::solana_program::keccak::hashv(&[&index, &claimant, &amount]).0,
+        _ => {
+            anchor_lang::solana_program::keccak::hashv(&[&index, &claimant, &amount, &[mint_index]])
+                .0
+        }
+    }
+}
@@ -1 +1,4 @@
+pub mod account;
+pub mod ed25519;
 pub mod merkle_proof;
+pub mod token;
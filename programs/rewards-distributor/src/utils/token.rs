@@ -0,0 +1,68 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+};
+
+/// Checks that the extensions of `mint` let the program hold and distribute its tokens.
+///
+/// Legacy SPL Token mints have no extensions and are always supported.
+pub fn assert_supported_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    for extension in mint.get_extension_types()? {
+        match extension {
+            ExtensionType::NonTransferable => return err!(ErrorCode::NonTransferableMint),
+            ExtensionType::PermanentDelegate => return err!(ErrorCode::PermanentDelegateMint),
+            ExtensionType::TransferHook => return err!(ErrorCode::TransferHookMint),
+            ExtensionType::ConfidentialTransferMint => {
+                return err!(ErrorCode::ConfidentialTransferMint)
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the [TransferFeeConfig] of `mint`, if it has one.
+pub fn transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(mint.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// Returns the fee withheld by `mint` when transferring `amount`, 0 for mints without transfer fee.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let Some(config) = transfer_fee_config(mint)? else {
+        return Ok(0);
+    };
+
+    config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or_else(|| error!(ErrorCode::InvalidTransferFee))
+}
+
+/// Returns the amount to transfer for `net_amount` to arrive once `mint` withheld its fee.
+pub fn gross_amount(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    let Some(config) = transfer_fee_config(mint)? else {
+        return Ok(net_amount);
+    };
+
+    config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+        .and_then(|fee| net_amount.checked_add(fee))
+        .ok_or_else(|| error!(ErrorCode::InvalidTransferFee))
+}
@@ -7,10 +7,10 @@ import { MerkleTree } from "./merkle-tree";
 
 export class BalanceTree {
   private readonly _tree: MerkleTree;
-  constructor(balances: { account: PublicKey; amount: BN }[]) {
+  constructor(balances: { account: PublicKey; amount: BN; mintIndex?: number }[]) {
     this._tree = new MerkleTree(
-      balances.map(({ account, amount }, index) => {
-        return BalanceTree.toNode(index, account, amount);
+      balances.map(({ account, amount, mintIndex }, index) => {
+        return BalanceTree.toNode(index, account, amount, mintIndex);
       })
     );
   }
@@ -20,9 +20,10 @@ export class BalanceTree {
     account: PublicKey,
     amount: BN,
     proof: Buffer[],
-    root: Buffer
+    root: Buffer,
+    mintIndex = 0
   ): boolean {
-    let pair = BalanceTree.toNode(index, account, amount);
+    let pair = BalanceTree.toNode(index, account, amount, mintIndex);
     for (const item of proof) {
       pair = MerkleTree.combinedHash(pair, item);
     }
@@ -30,14 +31,18 @@ export class BalanceTree {
     return pair.equals(root);
   }
 
-  // keccak256(abi.encode(index, account, amount))
-  static toNode(index: number, account: PublicKey, amount: BN): Buffer {
-    const buf = Buffer.concat([
+  // keccak256(abi.encode(index, account, amount)) for the primary mint of the epoch,
+  // keccak256(abi.encode(index, account, amount, mintIndex)) for the other mints
+  static toNode(index: number, account: PublicKey, amount: BN, mintIndex = 0): Buffer {
+    const parts = [
       new u64(index).toArrayLike(Buffer, "le", 8),
       account.toBuffer(),
       new u64(amount).toArrayLike(Buffer, "le", 8),
-    ]);
-    return Buffer.from(keccak_256(buf), "hex");
+    ];
+    if (mintIndex > 0) {
+      parts.push(Buffer.from([mintIndex]));
+    }
+    return Buffer.from(keccak_256(Buffer.concat(parts)), "hex");
   }
 
   getHexRoot(): string {
@@ -45,15 +50,15 @@ export class BalanceTree {
   }
 
   // returns the hex bytes32 values of the proof
-  getHexProof(index: number, account: PublicKey, amount: BN): string[] {
-    return this._tree.getHexProof(BalanceTree.toNode(index, account, amount));
+  getHexProof(index: number, account: PublicKey, amount: BN, mintIndex = 0): string[] {
+    return this._tree.getHexProof(BalanceTree.toNode(index, account, amount, mintIndex));
   }
 
   getRoot(): Buffer {
     return this._tree.getRoot();
   }
 
-  getProof(index: number, account: PublicKey, amount: BN): Buffer[] {
-    return this._tree.getProof(BalanceTree.toNode(index, account, amount));
+  getProof(index: number, account: PublicKey, amount: BN, mintIndex = 0): Buffer[] {
+    return this._tree.getProof(BalanceTree.toNode(index, account, amount, mintIndex));
   }
 }
@@ -42,3 +42,141 @@ export const deriveEpochPDA = ({rewardsAccountKey, epochNr}: {rewardsAccountKey:
     PROGRAM_ID
   );
 };
+
+/**
+ * User claim state account address, used in cumulative distribution mode.
+ * @param rewardsAccountKey Rewards account public key.
+ * @param receiver Receiver public key.
+ * @param program Program.
+ */
+export const findUserClaimStateKey = ({rewardsAccountKey, receiver, program}: {
+  rewardsAccountKey: PublicKey,
+  receiver: PublicKey,
+  program: PublicKey
+}): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("UserClaimState"),
+      rewardsAccountKey.toBytes(),
+      receiver.toBytes(),
+    ],
+    program
+  );
+};
+
+/**
+ * Claim bitmap shard account address.
+ * @param epochAccount Epoch account public key.
+ * @param shard Shard index.
+ * @param program Program.
+ */
+export const findClaimBitmapKey = ({epochAccount, shard, program}: {
+  epochAccount: PublicKey,
+  shard: u64,
+  program: PublicKey
+}): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("ClaimBitmap"),
+      epochAccount.toBytes(),
+      shard.toArrayLike(Buffer, "le", 8),
+    ],
+    program
+  );
+};
+
+/**
+ * Lamport vault address of a native SOL epoch.
+ * @param rewardsAccountKey Rewards account public key.
+ * @param epochNr Epoch number.
+ */
+export const deriveEpochVaultPDA = ({rewardsAccountKey, epochNr}: {rewardsAccountKey: PublicKey, epochNr: u64}): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("EpochVault"),
+      rewardsAccountKey.toBytes(),
+      epochNr.toArrayLike(Buffer, "le", 8),
+    ],
+    PROGRAM_ID
+  );
+};
+
+/**
+ * Treasury vault address of a mint.
+ * @param rewardsAccountKey Rewards account public key.
+ * @param mint Mint public key.
+ */
+export const deriveTreasuryVaultPDA = ({rewardsAccountKey, mint}: {rewardsAccountKey: PublicKey, mint: PublicKey}): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("TreasuryVault"),
+      rewardsAccountKey.toBytes(),
+      mint.toBytes(),
+    ],
+    PROGRAM_ID
+  );
+};
+
+/**
+ * Address of the PDA holding the mint authority of the mints in mint mode.
+ * @param rewardsAccountKey Rewards account public key.
+ */
+export const deriveMintAuthorityPDA = ({rewardsAccountKey}: {rewardsAccountKey: PublicKey}): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("MintAuthority"),
+      rewardsAccountKey.toBytes(),
+    ],
+    PROGRAM_ID
+  );
+};
+
+/**
+ * Manager proposal account address.
+ * @param rewardsAccountKey Rewards account public key.
+ * @param proposalNr Proposal number.
+ */
+export const deriveManagerProposalPDA = ({rewardsAccountKey, proposalNr}: {rewardsAccountKey: PublicKey, proposalNr: u64}): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("ManagerProposal"),
+      rewardsAccountKey.toBytes(),
+      proposalNr.toArrayLike(Buffer, "le", 8),
+    ],
+    PROGRAM_ID
+  );
+};
+
+/**
+ * Role grant account address.
+ * @param rewardsAccountKey Rewards account public key.
+ * @param role Index of the role (0 Submitter, 1 Approver, 2 Pauser, 3 Funder, 4 ConfigAdmin).
+ * @param member Member public key.
+ */
+export const deriveRoleGrantPDA = ({rewardsAccountKey, role, member}: {rewardsAccountKey: PublicKey, role: number, member: PublicKey}): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("RoleGrant"),
+      rewardsAccountKey.toBytes(),
+      Uint8Array.from([role]),
+      member.toBytes(),
+    ],
+    PROGRAM_ID
+  );
+};
+
+/**
+ * Timelock operation account address.
+ * @param rewardsAccountKey Rewards account public key.
+ * @param operationNr Operation number.
+ */
+export const deriveTimelockOperationPDA = ({rewardsAccountKey, operationNr}: {rewardsAccountKey: PublicKey, operationNr: u64}): [PublicKey, number] => {
+  return PublicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("TimelockOperation"),
+      rewardsAccountKey.toBytes(),
+      operationNr.toArrayLike(Buffer, "le", 8),
+    ],
+    PROGRAM_ID
+  );
+};
@@ -0,0 +1,206 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA} from "../src/utils/pda";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("agent quorum tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const agent1Keypair = Keypair.generate();
+    const agent2Keypair = Keypair.generate();
+    const agent3Keypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each agent
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA and mint to it
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Airdrops
+        for (const keypair of [agent1Keypair, agent2Keypair, agent3Keypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should only approve an epoch once a quorum of agents submitted its root", async () => {
+        // 2-of-3 agent quorum
+        await program.methods.setAgentQuorum([
+            agent1Keypair.publicKey,
+            agent2Keypair.publicKey,
+            agent3Keypair.publicKey,
+        ], 2).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        const epochNr = new anchor.BN(1)
+        const amount = new anchor.BN(10)
+        const numNodes = new anchor.BN(1)
+        const treeRoot = Array(32).fill(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+
+        // The single agent can no longer add epochs
+        try {
+            await program.methods.addEpoch(epoch1Bump, treeRoot, amount, numNodes, "", Array(32).fill(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                currentEpochAccount: epoch1,
+                mint,
+                agent: agentKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([agentKeypair]).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        // Agent 1 adds the epoch, which counts as its submission
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, numNodes, "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agent1Keypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agent1Keypair]).rpc();
+
+        const approveEpoch = async () => {
+            await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+                managerTokenAccount,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+        try {
+            await approveEpoch()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "AgentQuorumNotReached")
+        }
+
+        const submitEpochRoot = async (keypair: Keypair, root: number[], rootMint: PublicKey = mint) => {
+            await program.methods.submitEpochRoot(epochNr, root, amount, numNodes, rootMint).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                agent: keypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([keypair]).rpc();
+        }
+
+        // Agent 2 computed a different root, its correction only counts as a divergence
+        await program.methods.correctEpoch(epochNr, Array(32).fill(2), amount, numNodes, "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            mint,
+            agent: agent2Keypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agent2Keypair]).rpc();
+        let epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.deepEqual(epochAccount.hash, treeRoot)
+        assert.equal(epochAccount.correctionCount.toNumber(), 0)
+        assert.equal(epochAccount.rootSubmissions.length, 2)
+        assert.equal(epochAccount.divergenceCount.toNumber(), 1)
+        try {
+            await approveEpoch()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "AgentQuorumNotReached")
+        }
+
+        // Agent 3 submits the same root for another mint, which diverges too
+        await submitEpochRoot(agent3Keypair, treeRoot, Keypair.generate().publicKey)
+        try {
+            await approveEpoch()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "AgentQuorumNotReached")
+        }
+
+        // Agent 3 submits the same root and mint, reaching the quorum
+        await submitEpochRoot(agent3Keypair, treeRoot)
+        await approveEpoch()
+        epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(epochAccount.isApproved)
+        assert.equal(epochAccount.divergenceCount.toNumber(), 2)
+
+        // Submissions are closed once the epoch is approved
+        try {
+            await submitEpochRoot(agent2Keypair, treeRoot)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochShouldNotBeApproved")
+        }
+    })
+});
@@ -114,7 +114,7 @@ describe("claim tests", () => {
             rewardsAccountKey: rewardsAccountKeypair.publicKey,
             epochNr: new anchor.BN(1),
         });
-        await program.methods.addEpoch(epoch1Bump, treeRoot).accounts({
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
             rewardsAccount: rewardsAccountKeypair.publicKey,
             currentEpochAccount: epoch1,
             mint,
@@ -123,7 +123,7 @@ describe("claim tests", () => {
         }).signers([agentKeypair]).rpc();
 
         // Approve epoch 1
-        await program.methods.approveEpoch(new anchor.BN(1), amount).accounts({
+        await program.methods.approveEpoch(new anchor.BN(1), amount, treeRoot, new anchor.BN(0)).accounts({
             rewardsAccount: rewardsAccountKeypair.publicKey,
             epochAccount: epoch1,
             manager: managerKeypair.publicKey,
@@ -139,7 +139,7 @@ describe("claim tests", () => {
             rewardsAccountKey: rewardsAccountKeypair.publicKey,
             epochNr: new anchor.BN(2),
         });
-        await program.methods.addEpoch(epoch2Bump, treeRoot).accounts({
+        await program.methods.addEpoch(epoch2Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
             rewardsAccount: rewardsAccountKeypair.publicKey,
             currentEpochAccount: epoch2,
             mint,
@@ -148,7 +148,7 @@ describe("claim tests", () => {
         }).signers([agentKeypair]).rpc()
 
         // Approve epoch 2
-        await program.methods.approveEpoch(new anchor.BN(2), amount).accounts({
+        await program.methods.approveEpoch(new anchor.BN(2), amount, treeRoot, new anchor.BN(0)).accounts({
             rewardsAccount: rewardsAccountKeypair.publicKey,
             epochAccount: epoch2,
             manager: managerKeypair.publicKey,
@@ -167,7 +167,7 @@ describe("claim tests", () => {
         );
 
         // Claim for epoch 1
-        await program.methods.claim(leafIndex, amount, proof).accounts({
+        await program.methods.claim(leafIndex, amount, proof, 0).accounts({
             rewardsAccount: rewardsAccountKeypair.publicKey,
             epochAccount: epoch1,
             claimStatus: findClaimStatusKey({
@@ -185,7 +185,7 @@ describe("claim tests", () => {
         }).signers([receiverKeypair]).rpc();
 
         // Claim for epoch 2
-        await program.methods.claim(leafIndex, amount, proof).accounts({
+        await program.methods.claim(leafIndex, amount, proof, 0).accounts({
             rewardsAccount: rewardsAccountKeypair.publicKey,
             epochAccount: epoch2,
             claimStatus: findClaimStatusKey({
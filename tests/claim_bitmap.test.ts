@@ -0,0 +1,210 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimBitmapKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {AnchorError} from "@coral-xyz/anchor";
+import {assert, expect} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("claim bitmap tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+
+        // Mint to manager
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+            [], // Multi signers
+            undefined, // Confirm options
+            TOKEN_PROGRAM_ID
+        );
+
+        // Airdrops
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+
+        // Manager doesn't need airdrop because the manager is the Anchor wallet
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should track claims in the bitmap", async () => {
+        const receiverKeypair = Keypair.generate();
+
+        // Tree
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+
+        // Proof
+        const leafIndex = new anchor.BN(0)
+        const proof = tree.getProof(leafIndex.toNumber(), receiverKeypair.publicKey, amount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+
+        // Add epoch 1
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        // Allocate the first bitmap shard
+        const shard = new anchor.BN(0)
+        const [claimBitmap] = findClaimBitmapKey({
+            epochAccount: epoch1,
+            shard,
+            program: program.programId
+        })
+        await program.methods.initClaimBitmap(epochNr, shard).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            claimBitmap,
+            authority: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        // Approve epoch 1
+        await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+            managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).signers([managerKeypair]).rpc();
+
+        // Airdrop for claimer
+        await confirmedAirdrop(
+            provider.connection,
+            receiverKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+
+        const claim = async () => program.methods.claim(leafIndex, amount, proof, 0).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            claimStatus: null,
+            claimBitmap,
+            from: await getAssociatedTokenAddress(mint, epoch1, true),
+            to: await getAssociatedTokenAddress(mint, receiverKeypair.publicKey),
+            receiver: receiverKeypair.publicKey,
+            mintAccount: mint,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).signers([receiverKeypair]).rpc();
+
+        await claim();
+
+        const bitmap = await program.account.claimBitmap.fetch(claimBitmap)
+        assert.equal(bitmap.numClaimed.toNumber(), 1)
+        assert.equal(bitmap.bits[0], 1)
+        assert.isTrue(bitmap.payer.equals(agentKeypair.publicKey))
+
+        // Claiming the same leaf again fails
+        try {
+            await claim();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("DropAlreadyClaimed");
+        }
+
+        // Closing the fully claimed epoch closes the shard, the rent goes back to the agent
+        const agentBalanceBefore = await provider.connection.getBalance(agentKeypair.publicKey)
+        await program.methods.closeEpoch(epochNr).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+            creator: agentKeypair.publicKey,
+            approver: managerKeypair.publicKey,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).remainingAccounts([
+            {pubkey: claimBitmap, isWritable: true, isSigner: false},
+            {pubkey: agentKeypair.publicKey, isWritable: true, isSigner: false},
+        ]).rpc();
+        assert.isNull(await provider.connection.getAccountInfo(claimBitmap))
+        assert.isAbove(await provider.connection.getBalance(agentKeypair.publicKey), agentBalanceBefore)
+    })
+});
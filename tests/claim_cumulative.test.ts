@@ -0,0 +1,253 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, findUserClaimStateKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("claim cumulative tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        const managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+            [], // Multi signers
+            undefined, // Confirm options
+            TOKEN_PROGRAM_ID
+        );
+
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+        await confirmedAirdrop(
+            provider.connection,
+            receiverKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        rewardsAccountKeypair = Keypair.generate();
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+
+        await program.methods
+            .setDistributionMode({cumulative: {}})
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .rpc();
+    });
+
+    const addAndApproveEpoch = async (epochNr: anchor.BN, cumulativeAmount: anchor.BN, fundedAmount: anchor.BN): Promise<[PublicKey, BalanceTree]> => {
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount: cumulativeAmount,
+            },
+        ])
+        const [epoch, epochBump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+        await program.methods.addEpoch(epochBump, treeRoot, fundedAmount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        const [previousEpoch] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr: epochNr.subn(1),
+        });
+        const isFirstEpoch = epochNr.eqn(1)
+        await program.methods.approveEpoch(epochNr, fundedAmount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch, true),
+            managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+            mintAccount: mint,
+            previousEpochAccount: isFirstEpoch ? null : previousEpoch,
+            previousEpochTokenAccount: isFirstEpoch ? null : await getAssociatedTokenAddress(mint, previousEpoch, true),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).signers([managerKeypair]).rpc();
+
+        return [epoch, tree]
+    }
+
+    const claimCumulative = async (epoch: PublicKey, tree: BalanceTree, cumulativeAmount: anchor.BN, proofAmount = cumulativeAmount) => {
+        const leafIndex = new anchor.BN(0)
+        const proof = tree.getProof(leafIndex.toNumber(), receiverKeypair.publicKey, proofAmount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+        await program.methods.claimCumulative(leafIndex, cumulativeAmount, proof).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch,
+            userClaimState: findUserClaimStateKey({
+                rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                receiver: receiverKeypair.publicKey,
+                program: program.programId
+            })[0],
+            from: await getAssociatedTokenAddress(mint, epoch, true),
+            to: await getAssociatedTokenAddress(mint, receiverKeypair.publicKey),
+            receiver: receiverKeypair.publicKey,
+            mintAccount: mint,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).signers([receiverKeypair]).rpc();
+    }
+
+    it("should only pay the difference with what was already claimed", async () => {
+        // Epoch 1: 10 tokens in total
+        const [epoch1, tree1] = await addAndApproveEpoch(new anchor.BN(1), new anchor.BN(10), new anchor.BN(10))
+        await claimCumulative(epoch1, tree1, new anchor.BN(10))
+
+        // Epoch 2: 25 tokens in total, 15 new
+        const [epoch2, tree2] = await addAndApproveEpoch(new anchor.BN(2), new anchor.BN(25), new anchor.BN(15))
+        await claimCumulative(epoch2, tree2, new anchor.BN(25))
+
+        const to = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, receiverKeypair.publicKey))
+        assert.equal(Number(to.amount), 25)
+
+        const [userClaimState] = findUserClaimStateKey({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            receiver: receiverKeypair.publicKey,
+            program: program.programId
+        })
+        const state = await program.account.userClaimState.fetch(userClaimState)
+        assert.equal(state.claimedAmount.toNumber(), 25)
+    })
+
+    it("should not pay more than the lifetime entitlement", async () => {
+        const [epoch1, tree1] = await addAndApproveEpoch(new anchor.BN(1), new anchor.BN(10), new anchor.BN(10))
+
+        // The amount must match the leaf
+        try {
+            await claimCumulative(epoch1, tree1, new anchor.BN(20), new anchor.BN(10))
+            assert(false, "should've failed but didn't")
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "InvalidProof")
+        }
+
+        await claimCumulative(epoch1, tree1, new anchor.BN(10))
+
+        // Nothing is left once the entitlement is claimed
+        try {
+            await claimCumulative(epoch1, tree1, new anchor.BN(10))
+            assert(false, "should've failed but didn't")
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "NothingToClaim")
+        }
+
+        const [userClaimState] = findUserClaimStateKey({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            receiver: receiverKeypair.publicKey,
+            program: program.programId
+        })
+        const state = await program.account.userClaimState.fetch(userClaimState)
+        assert.equal(state.claimedAmount.toNumber(), 10)
+    })
+
+    it("should give the carried over balance back to the previous epoch when vetoed", async () => {
+        // Epoch 1: 10 tokens in total, left unclaimed
+        const [epoch1] = await addAndApproveEpoch(new anchor.BN(1), new anchor.BN(10), new anchor.BN(10))
+
+        // Epoch 2 can be vetoed during its challenge window
+        await program.methods.setChallengeWindow(new anchor.BN(3600)).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        // Epoch 2: 25 tokens in total, 15 new and 10 carried over from epoch 1
+        const epochNr = new anchor.BN(2)
+        const [epoch2] = await addAndApproveEpoch(epochNr, new anchor.BN(25), new anchor.BN(15))
+        let epochAccount = await program.account.epochAccount.fetch(epoch2)
+        assert.equal(epochAccount.carriedOver.toNumber(), 10)
+
+        const epoch1TokenAccount = await getAssociatedTokenAddress(mint, epoch1, true)
+        const epoch2TokenAccount = await getAssociatedTokenAddress(mint, epoch2, true)
+        await program.methods.vetoEpoch(epochNr).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch2,
+            authority: managerKeypair.publicKey,
+            epochTokenAccount: epoch2TokenAccount,
+            managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+            mintAccount: mint,
+            previousEpochAccount: epoch1,
+            previousEpochTokenAccount: epoch1TokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).rpc();
+
+        // The carried over tokens are back in epoch 1, the new ones with the manager
+        assert.equal(Number((await getAccount(provider.connection, epoch1TokenAccount)).amount), 10)
+        assert.equal(Number((await getAccount(provider.connection, epoch2TokenAccount)).amount), 0)
+        epochAccount = await program.account.epochAccount.fetch(epoch2)
+        assert.equal(epochAccount.carriedOver.toNumber(), 0)
+    })
+});
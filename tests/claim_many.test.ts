@@ -0,0 +1,313 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("claim many tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+
+        // Mint to manager
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+            [], // Multi signers
+            undefined, // Confirm options
+            TOKEN_PROGRAM_ID
+        );
+
+        // Airdrops
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+
+        // Manager doesn't need airdrop because the manager is the Anchor wallet
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        rewardsAccountKeypair = Keypair.generate();
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should claim from multiple epochs in one instruction", async () => {
+        const receiverKeypair = Keypair.generate();
+
+        // Tree
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+
+        // Proof
+        const leafIndex = new anchor.BN(0)
+        const proof = tree.getProof(leafIndex.toNumber(), receiverKeypair.publicKey, amount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+
+        // Add epoch 1
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr: new anchor.BN(1),
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        // Approve epoch 1
+        await program.methods.approveEpoch(new anchor.BN(1), amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+            managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).signers([managerKeypair]).rpc();
+
+        // Add epoch 2
+        const [epoch2, epoch2Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr: new anchor.BN(2),
+        });
+        await program.methods.addEpoch(epoch2Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch2,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc()
+
+        // Approve epoch 2
+        await program.methods.approveEpoch(new anchor.BN(2), amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch2,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch2, true),
+            managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).signers([managerKeypair]).rpc();
+
+        // Airdrop for claimer
+        await confirmedAirdrop(
+            provider.connection,
+            receiverKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+
+        // Claim epochs 1 and 2 at once
+        const epochs = [epoch1, epoch2]
+        const remainingAccounts = []
+        for (const epoch of epochs) {
+            remainingAccounts.push(
+                {pubkey: epoch, isSigner: false, isWritable: true},
+                {pubkey: await getAssociatedTokenAddress(mint, epoch, true), isSigner: false, isWritable: true},
+                {
+                    pubkey: findClaimStatusKey({
+                        rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                        index: leafIndex,
+                        epochAccount: epoch,
+                        program: program.programId
+                    })[0],
+                    isSigner: false,
+                    isWritable: true
+                },
+            )
+        }
+        const to = await getAssociatedTokenAddress(mint, receiverKeypair.publicKey)
+        await program.methods.claimMany([
+            {epochNr: new anchor.BN(1), index: leafIndex, amount, proof, mintIndex: 0},
+            {epochNr: new anchor.BN(2), index: leafIndex, amount, proof, mintIndex: 0},
+        ]).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            to,
+            receiver: receiverKeypair.publicKey,
+            mintAccount: mint,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).remainingAccounts(remainingAccounts).signers([receiverKeypair]).rpc();
+
+        const receiverTokenAccount = await getAccount(provider.connection, to)
+        assert.equal(Number(receiverTokenAccount.amount), amount.toNumber() * 2)
+    })
+
+    it("should name the failing entry of a batch", async () => {
+        const receiverKeypair = Keypair.generate();
+
+        // Tree
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+
+        // Proof
+        const leafIndex = new anchor.BN(0)
+        const proof = tree.getProof(leafIndex.toNumber(), receiverKeypair.publicKey, amount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+
+        // Add and approve epochs 1 and 2
+        const epochs: PublicKey[] = []
+        for (const epochNr of [new anchor.BN(1), new anchor.BN(2)]) {
+            const [epoch, epochBump] = deriveEpochPDA({
+                rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                epochNr,
+            });
+            await program.methods.addEpoch(epochBump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                currentEpochAccount: epoch,
+                mint,
+                agent: agentKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([agentKeypair]).rpc();
+            await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch, true),
+                managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).signers([managerKeypair]).rpc();
+            epochs.push(epoch)
+        }
+
+        // Airdrop for claimer
+        await confirmedAirdrop(
+            provider.connection,
+            receiverKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+
+        const remainingAccounts = []
+        for (const epoch of epochs) {
+            remainingAccounts.push(
+                {pubkey: epoch, isSigner: false, isWritable: true},
+                {pubkey: await getAssociatedTokenAddress(mint, epoch, true), isSigner: false, isWritable: true},
+                {
+                    pubkey: findClaimStatusKey({
+                        rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                        index: leafIndex,
+                        epochAccount: epoch,
+                        program: program.programId
+                    })[0],
+                    isSigner: false,
+                    isWritable: true
+                },
+            )
+        }
+        const claimMany = async (secondAmount: anchor.BN) => {
+            await program.methods.claimMany([
+                {epochNr: new anchor.BN(1), index: leafIndex, amount, proof, mintIndex: 0},
+                {epochNr: new anchor.BN(2), index: leafIndex, amount: secondAmount, proof, mintIndex: 0},
+            ]).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                to: await getAssociatedTokenAddress(mint, receiverKeypair.publicKey),
+                receiver: receiverKeypair.publicKey,
+                mintAccount: mint,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).remainingAccounts(remainingAccounts).signers([receiverKeypair]).rpc();
+        }
+
+        // The second entry does not match its proof, the error carries its position
+        try {
+            await claimMany(amount.addn(1))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ClaimEntryFailed")
+            assert.equal(_err.error.comparedValues[0], "1")
+            assert.include(_err.error.comparedValues[1], "InvalidProof")
+        }
+
+        // Once claimed, the first entry fails the batch
+        await claimMany(amount)
+        try {
+            await claimMany(amount)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ClaimEntryFailed")
+            assert.equal(_err.error.comparedValues[0], "0")
+            assert.include(_err.error.comparedValues[1], "DropAlreadyClaimed")
+        }
+    })
+});
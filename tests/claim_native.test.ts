@@ -0,0 +1,274 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, deriveEpochVaultPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {confirmedAirdrop} from "./utils";
+import {AnchorError} from "@coral-xyz/anchor";
+import {assert, expect} from "chai";
+
+const {
+    createMint,
+} = require("@solana/spl-token");
+
+describe("claim native tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    before("do airdrops", async () => {
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+        await confirmedAirdrop(
+            provider.connection,
+            receiverKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        rewardsAccountKeypair = Keypair.generate();
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should claim lamports from a native SOL epoch", async () => {
+        // Tree, amounts in lamports
+        const amount = new anchor.BN(LAMPORTS_PER_SOL / 2)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+        const leafIndex = new anchor.BN(0)
+        const proof = tree.getProof(leafIndex.toNumber(), receiverKeypair.publicKey, amount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+
+        // Add epoch 1 without mint and approve it
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        const [vault] = deriveEpochVaultPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint: null,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        await program.methods.approveNativeEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            vault,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(epochAccount.isNative)
+        const rent = await provider.connection.getMinimumBalanceForRentExemption(0)
+        assert.equal(await provider.connection.getBalance(vault), amount.toNumber() + rent)
+
+        // Claim
+        const claimNative = async () => {
+            await program.methods.claimNative(leafIndex, amount, proof).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimStatus: findClaimStatusKey({
+                    rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                    index: leafIndex,
+                    epochAccount: epoch1,
+                    program: program.programId
+                })[0],
+                vault,
+                receiver: receiverKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([receiverKeypair]).rpc();
+        }
+        await claimNative()
+
+        // The vault keeps its rent-exempt minimum
+        assert.equal(await provider.connection.getBalance(vault), rent)
+
+        // The drop cannot be claimed twice
+        try {
+            await claimNative()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.isTrue(_err.toString().includes("already in use"))
+        }
+
+        // The fully claimed epoch is closed and its vault emptied
+        await program.methods.closeNativeEpoch(epochNr).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            vault,
+            creator: agentKeypair.publicKey,
+            approver: managerKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+        assert.isNull(await provider.connection.getAccountInfo(epoch1))
+        assert.equal(await provider.connection.getBalance(vault), 0)
+    })
+
+    it("should sweep the unclaimed lamports of a native SOL epoch", async () => {
+        const treasuryKeypair = Keypair.generate();
+        const amount = new anchor.BN(LAMPORTS_PER_SOL / 2)
+        const treeRoot = Array.from(Uint8Array.from(new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ]).getRoot()))
+
+        // One second claim window and a treasury
+        await program.methods.setClaimWindow(new anchor.BN(1)).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+        await program.methods.setTreasury(treasuryKeypair.publicKey).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        // Add and approve epoch 1
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        const [vault] = deriveEpochVaultPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint: null,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        await program.methods.approveNativeEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            vault,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+
+        const closeNativeEpoch = async () => {
+            await program.methods.closeNativeEpoch(epochNr).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                vault,
+                creator: agentKeypair.publicKey,
+                approver: managerKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+
+        // The unclaimed lamports keep the epoch open
+        try {
+            await closeNativeEpoch()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("EpochVaultNotEmpty");
+        }
+
+        // Let the deadline pass and sweep, the vault keeps its rent-exempt minimum
+        await new Promise((resolve) => setTimeout(resolve, 3000));
+        await program.methods.sweepNativeEpoch(epochNr).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            vault,
+            treasury: treasuryKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+        const rent = await provider.connection.getMinimumBalanceForRentExemption(0)
+        assert.equal(await provider.connection.getBalance(treasuryKeypair.publicKey), amount.toNumber())
+        assert.equal(await provider.connection.getBalance(vault), rent)
+        assert.isTrue((await program.account.epochAccount.fetch(epoch1)).isSwept)
+
+        await closeNativeEpoch()
+        assert.equal(await provider.connection.getBalance(vault), 0)
+    })
+
+    it("should not approve a token epoch as native", async () => {
+        const mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, Array(32).fill(0), new anchor.BN(1), new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        try {
+            await program.methods.approveNativeEpoch(epochNr, new anchor.BN(1), Array(32).fill(0), new anchor.BN(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                vault: deriveEpochVaultPDA({
+                    rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                    epochNr,
+                })[0],
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("NotNativeEpoch");
+        }
+    })
+});
@@ -0,0 +1,283 @@
+import * as anchor from "@coral-xyz/anchor";
+import {AnchorError, Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {
+    Ed25519Program,
+    Keypair,
+    PublicKey,
+    SystemProgram,
+    SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {assert, expect} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+/**
+ * Builds the message a receiver signs to authorize a relayed claim.
+ */
+const claimAuthorizationMessage = ({programId, rewardsAccount, epochNr, index, amount, destination, expiry}: {
+    programId: PublicKey,
+    rewardsAccount: PublicKey,
+    epochNr: anchor.BN,
+    index: anchor.BN,
+    amount: anchor.BN,
+    destination: PublicKey,
+    expiry: anchor.BN,
+}): Buffer => {
+    return Buffer.concat([
+        programId.toBuffer(),
+        rewardsAccount.toBuffer(),
+        epochNr.toArrayLike(Buffer, "le", 8),
+        index.toArrayLike(Buffer, "le", 8),
+        amount.toArrayLike(Buffer, "le", 8),
+        destination.toBuffer(),
+        expiry.toTwos(64).toArrayLike(Buffer, "le", 8),
+    ]);
+};
+
+describe("claim with authorization tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const relayerKeypair = meKeypair
+    const agentKeypair = meKeypair
+
+    // Mint
+    let mint: PublicKey
+
+    // Create the mint and fund the manager
+    before("create mint", async () => {
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        const managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+            [], // Multi signers
+            undefined, // Confirm options
+            TOKEN_PROGRAM_ID
+        );
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        rewardsAccountKeypair = Keypair.generate();
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    // Tree
+    const amount = new anchor.BN(10)
+    const leafIndex = new anchor.BN(0)
+    const epochNr = new anchor.BN(1)
+
+    /**
+     * Adds and approves epoch 1 paying `amount` to `receiver`, returning its PDA and the proof.
+     */
+    const approveEpochFor = async (receiver: PublicKey): Promise<[PublicKey, number[][]]> => {
+        const tree = new BalanceTree([
+            {
+                account: receiver,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+        const proof = tree.getProof(leafIndex.toNumber(), receiver, amount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+            managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).signers([managerKeypair]).rpc();
+
+        return [epoch1, proof]
+    }
+
+    /**
+     * Signs the authorization of the claim of `receiver` with `signer`, off-chain.
+     */
+    const signAuthorization = (signer: Keypair, destination: PublicKey, expiry: anchor.BN) => {
+        return Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: signer.secretKey,
+            message: claimAuthorizationMessage({
+                programId: program.programId,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochNr,
+                index: leafIndex,
+                amount,
+                destination,
+                expiry,
+            }),
+        })
+    }
+
+    /**
+     * Submits the claim of `receiver` as the relayer, after the `ed25519Ix` verification.
+     */
+    const relayClaim = async (
+        receiver: PublicKey,
+        epoch1: PublicKey,
+        proof: number[][],
+        expiry: anchor.BN,
+        ed25519Ix: anchor.web3.TransactionInstruction
+    ) => {
+        await program.methods.claimWithAuthorization(leafIndex, amount, proof, expiry, 0).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            claimStatus: findClaimStatusKey({
+                rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                index: leafIndex,
+                epochAccount: epoch1,
+                program: program.programId
+            })[0],
+            from: await getAssociatedTokenAddress(mint, epoch1, true),
+            to: await getAssociatedTokenAddress(mint, receiver),
+            receiver,
+            relayer: relayerKeypair.publicKey,
+            mintAccount: mint,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).preInstructions([ed25519Ix]).signers([relayerKeypair]).rpc();
+    }
+
+    it("should let a relayer claim for a receiver without SOL", async () => {
+        // The receiver never gets an airdrop
+        const receiverKeypair = Keypair.generate();
+        const [epoch1, proof] = await approveEpochFor(receiverKeypair.publicKey)
+
+        // Receiver signs the authorization off-chain
+        const destination = await getAssociatedTokenAddress(mint, receiverKeypair.publicKey)
+        const expiry = new anchor.BN(Math.floor(Date.now() / 1000) + 3600)
+        const ed25519Ix = signAuthorization(receiverKeypair, destination, expiry)
+
+        // Relayer submits the claim
+        await relayClaim(receiverKeypair.publicKey, epoch1, proof, expiry, ed25519Ix)
+
+        const receiverTokenAccount = await getAccount(provider.connection, destination)
+        assert.equal(Number(receiverTokenAccount.amount), amount.toNumber())
+        assert.equal(await provider.connection.getBalance(receiverKeypair.publicKey), 0)
+    })
+
+    it("should not claim with an expired authorization", async () => {
+        const receiverKeypair = Keypair.generate();
+        const [epoch1, proof] = await approveEpochFor(receiverKeypair.publicKey)
+
+        // The authorization expired an hour ago
+        const destination = await getAssociatedTokenAddress(mint, receiverKeypair.publicKey)
+        const expiry = new anchor.BN(Math.floor(Date.now() / 1000) - 3600)
+        const ed25519Ix = signAuthorization(receiverKeypair, destination, expiry)
+
+        try {
+            await relayClaim(receiverKeypair.publicKey, epoch1, proof, expiry, ed25519Ix)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("AuthorizationExpired");
+        }
+    })
+
+    it("should not claim with an authorization signed by someone else than the receiver", async () => {
+        const receiverKeypair = Keypair.generate();
+        const [epoch1, proof] = await approveEpochFor(receiverKeypair.publicKey)
+
+        // The relayer signs the authorization in place of the receiver
+        const destination = await getAssociatedTokenAddress(mint, receiverKeypair.publicKey)
+        const expiry = new anchor.BN(Math.floor(Date.now() / 1000) + 3600)
+        const ed25519Ix = signAuthorization(relayerKeypair, destination, expiry)
+
+        try {
+            await relayClaim(receiverKeypair.publicKey, epoch1, proof, expiry, ed25519Ix)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("InvalidAuthorization");
+        }
+    })
+
+    it("should not claim with parameters differing from the signed message", async () => {
+        const receiverKeypair = Keypair.generate();
+        const [epoch1, proof] = await approveEpochFor(receiverKeypair.publicKey)
+
+        // The receiver signs a short expiry which the relayer extends
+        const destination = await getAssociatedTokenAddress(mint, receiverKeypair.publicKey)
+        const signedExpiry = new anchor.BN(Math.floor(Date.now() / 1000) + 60)
+        const ed25519Ix = signAuthorization(receiverKeypair, destination, signedExpiry)
+
+        try {
+            await relayClaim(receiverKeypair.publicKey, epoch1, proof, signedExpiry.addn(3600), ed25519Ix)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("InvalidAuthorization");
+        }
+
+        // Nothing was claimed
+        const epochTokenAccount = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, epoch1, true))
+        assert.equal(Number(epochTokenAccount.amount), amount.toNumber())
+    })
+});
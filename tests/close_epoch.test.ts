@@ -0,0 +1,207 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {AnchorError} from "@coral-xyz/anchor";
+import {assert, expect} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("close epoch tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        const managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+            [], // Multi signers
+            undefined, // Confirm options
+            TOKEN_PROGRAM_ID
+        );
+
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+        await confirmedAirdrop(
+            provider.connection,
+            receiverKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should close a fully claimed epoch and its claim statuses", async () => {
+        // Tree
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+        const leafIndex = new anchor.BN(0)
+        const proof = tree.getProof(leafIndex.toNumber(), receiverKeypair.publicKey, amount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+
+        // Add and approve epoch 1
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        const epochTokenAccount = await getAssociatedTokenAddress(mint, epoch1, true)
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount,
+            managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).signers([managerKeypair]).rpc();
+
+        // Claim everything
+        const [claimStatus] = findClaimStatusKey({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            index: leafIndex,
+            epochAccount: epoch1,
+            program: program.programId
+        })
+        const claim = async () => {
+            await program.methods.claim(leafIndex, amount, proof, 0).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimStatus,
+                from: epochTokenAccount,
+                to: await getAssociatedTokenAddress(mint, receiverKeypair.publicKey),
+                receiver: receiverKeypair.publicKey,
+                mintAccount: mint,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).signers([receiverKeypair]).rpc();
+        }
+        await claim()
+
+        const closeClaimStatus = async () => {
+            await program.methods.closeClaimStatus(leafIndex).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimStatus,
+                payer: receiverKeypair.publicKey,
+            }).rpc();
+        }
+
+        // The claim status is kept while the epoch can be claimed
+        try {
+            await closeClaimStatus()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("EpochStillClaimable");
+        }
+
+        // Close the epoch, the rent goes back to the agent
+        const agentBalanceBefore = await provider.connection.getBalance(agentKeypair.publicKey)
+        await program.methods.closeEpoch(epochNr).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount,
+            creator: agentKeypair.publicKey,
+            approver: managerKeypair.publicKey,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).rpc();
+        assert.isNull(await provider.connection.getAccountInfo(epoch1))
+        assert.isNull(await provider.connection.getAccountInfo(epochTokenAccount))
+        assert.isAbove(await provider.connection.getBalance(agentKeypair.publicKey), agentBalanceBefore)
+
+        // Now the claim status can be closed, the rent goes back to the receiver
+        const receiverBalanceBefore = await provider.connection.getBalance(receiverKeypair.publicKey)
+        await closeClaimStatus()
+        assert.isNull(await provider.connection.getAccountInfo(claimStatus))
+        assert.isAbove(await provider.connection.getBalance(receiverKeypair.publicKey), receiverBalanceBefore)
+
+        // The drop cannot be claimed again
+        try {
+            await claim()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+        }
+    })
+});
@@ -7,11 +7,15 @@ import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.
 import {deriveEpochPDA} from "../src/utils/pda";
 import {BalanceTree} from "../src/libs/balance-tree";
 import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
 import {confirmedAirdrop} from "./utils";
 import {assert, expect} from "chai";
 
 const {
     createMint,
+    createAccount,
+    mintTo,
+    getAssociatedTokenAddress,
 } = require("@solana/spl-token");
 
 describe("corrections tests", () => {
@@ -51,6 +55,22 @@ describe("corrections tests", () => {
             0 // Decimals
         );
 
+        // Create the manager ATA of the correct mint and mint to it
+        const managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            correctMint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            correctMint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
         // Airdrops
         await confirmedAirdrop(
             provider.connection,
@@ -94,7 +114,7 @@ describe("corrections tests", () => {
             rewardsAccountKey: rewardsAccountKeypair.publicKey,
             epochNr: new anchor.BN(1),
         });
-        await program.methods.addEpoch(epoch1Bump, wrongTreeRoot).accounts({
+        await program.methods.addEpoch(epoch1Bump, wrongTreeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
             rewardsAccount: rewardsAccountKeypair.publicKey,
             currentEpochAccount: epoch1,
             mint: wrongMint,
@@ -109,7 +129,7 @@ describe("corrections tests", () => {
         );
 
         // Correct epoch
-        await program.methods.correctEpoch(new anchor.BN(1), correctTreeRoot).accounts({
+        await program.methods.correctEpoch(new anchor.BN(1), correctTreeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
             agent: agentKeypair.publicKey,
             mint: correctMint,
             rewardsAccount: rewardsAccountKeypair.publicKey,
@@ -122,5 +142,35 @@ describe("corrections tests", () => {
             correctedEpochAccount.mint.equals(correctMint),
             "Mint should be the correct one"
         );
+        assert.equal(correctedEpochAccount.correctionCount.toNumber(), 1)
+
+        // The manager cannot approve the epoch as it was before the correction
+        const approveEpoch = async (root: number[], correctionCount: anchor.BN) => {
+            await program.methods.approveEpoch(new anchor.BN(1), amount, root, correctionCount).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(correctMint, epoch1, true),
+                managerTokenAccount: await getAssociatedTokenAddress(correctMint, managerKeypair.publicKey),
+                mintAccount: correctMint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+        for (const [root, correctionCount] of [
+            [wrongTreeRoot, new anchor.BN(0)],
+            [correctTreeRoot, new anchor.BN(0)],
+        ] as [number[], anchor.BN][]) {
+            try {
+                await approveEpoch(root, correctionCount)
+                assert(false, "should've failed but didn't");
+            } catch (_err) {
+                assert.equal(_err.error.errorCode.code, "EpochChangedSinceReview")
+            }
+        }
+
+        await approveEpoch(correctTreeRoot, new anchor.BN(1))
+        const approvedEpochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(approvedEpochAccount.isApproved)
     })
 });
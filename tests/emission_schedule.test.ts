@@ -0,0 +1,271 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, deriveTimelockOperationPDA} from "../src/utils/pda";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+// Pause bit of the admin operations
+const PAUSE_ADMIN = 8
+
+describe("emission schedule tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA and mint to it
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Airdrops
+        for (const keypair of [agentKeypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        rewardsAccountKeypair = Keypair.generate();
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    /**
+     * Returns an emission schedule of `scheduleMint` allowing 10 from epoch 1, decayed by `decayBps` every epoch.
+     */
+    const emissionSchedule = (scheduleMint: PublicKey, decayBps: number) => ({
+        mint: scheduleMint,
+        startEpoch: new anchor.BN(1),
+        initialAmount: new anchor.BN(10),
+        decayInterval: new anchor.BN(1),
+        decayBps: new anchor.BN(decayBps),
+    })
+
+    const setEmissionSchedule = async (scheduleMint: PublicKey, decayBps: number) => {
+        await program.methods.setEmissionSchedule(emissionSchedule(scheduleMint, decayBps)).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+    }
+
+    const treeRoot = Array(32).fill(1)
+    const addEpoch = async (epochNr: anchor.BN, maxTotalClaim: anchor.BN): Promise<PublicKey> => {
+        const [epoch, epochBump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epochBump, treeRoot, maxTotalClaim, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        return epoch
+    }
+    const approveEpoch = async (epochNr: anchor.BN, epoch: PublicKey, amount: anchor.BN, correctionCount: anchor.BN) => {
+        await program.methods.approveEpoch(epochNr, amount, treeRoot, correctionCount).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch, true),
+            managerTokenAccount,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+    }
+
+    it("should cap epochs by the emission schedule", async () => {
+        try {
+            await setEmissionSchedule(mint, 10_001)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "InvalidEmissionSchedule")
+        }
+
+        // Halving every epoch, from 10 at epoch 1
+        await setEmissionSchedule(mint, 5_000)
+
+        // Epoch 1 issues its full allowance
+        const epoch1Nr = new anchor.BN(1)
+        const epoch1 = await addEpoch(epoch1Nr, new anchor.BN(10))
+        await approveEpoch(epoch1Nr, epoch1, new anchor.BN(10), new anchor.BN(0))
+        const epoch1Account = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(epoch1Account.issuedAmount.toNumber(), 10)
+
+        // Epoch 2 is allowed half as much
+        const epoch2Nr = new anchor.BN(2)
+        const epoch2 = await addEpoch(epoch2Nr, new anchor.BN(10))
+        try {
+            await approveEpoch(epoch2Nr, epoch2, new anchor.BN(10), new anchor.BN(0))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EmissionScheduleExceeded")
+        }
+
+        await program.methods.correctEpoch(epoch2Nr, treeRoot, new anchor.BN(5), new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            agent: agentKeypair.publicKey,
+            mint,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch2,
+        }).signers([agentKeypair]).rpc();
+        await approveEpoch(epoch2Nr, epoch2, new anchor.BN(5), new anchor.BN(1))
+    })
+
+    it("should not cap epochs of another mint", async () => {
+        await setEmissionSchedule(Keypair.generate().publicKey, 0)
+
+        const epoch1Nr = new anchor.BN(1)
+        const epoch1 = await addEpoch(epoch1Nr, new anchor.BN(20))
+        await approveEpoch(epoch1Nr, epoch1, new anchor.BN(20), new anchor.BN(0))
+        const epoch1Account = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(epoch1Account.issuedAmount.toNumber(), 20)
+    })
+
+    it("should change the emission schedule through the timelock once enabled", async () => {
+        const operation = (operationNr: number): PublicKey => deriveTimelockOperationPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            operationNr: new anchor.BN(operationNr),
+        })[0];
+        const queue = async (operationNr: number, action: any) => {
+            await program.methods.queueTimelockOperation(action).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                operation: operation(operationNr),
+                manager: managerKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+        const execute = async (operationNr: number) => {
+            await program.methods.executeTimelockOperation(new anchor.BN(operationNr)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                operation: operation(operationNr),
+            }).rpc();
+        }
+
+        await setEmissionSchedule(mint, 5_000)
+        await queue(1, {setTimelockDelay: {delay: new anchor.BN(2)}})
+        await execute(1)
+
+        // The schedule can no longer be raised at once
+        try {
+            await setEmissionSchedule(mint, 10_000)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "TimelockRequired")
+        }
+
+        try {
+            await queue(2, {setEmissionSchedule: {emissionSchedule: emissionSchedule(mint, 10_001)}})
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "InvalidEmissionSchedule")
+        }
+
+        await queue(2, {setEmissionSchedule: {emissionSchedule: emissionSchedule(mint, 10_000)}})
+        try {
+            await execute(2)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "TimelockNotReady")
+        }
+
+        await new Promise((resolve) => setTimeout(resolve, 3000));
+        await execute(2)
+        const rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.emissionSchedule.decayBps.toNumber(), 10_000)
+        assert.isTrue(rewardsAccount.emissionSchedule.mint.equals(mint))
+    })
+
+    it("should only let the manager set the emission schedule while not paused", async () => {
+        // The agent cannot set it
+        try {
+            await program.methods.setEmissionSchedule(emissionSchedule(mint, 0)).accounts({
+                manager: agentKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).signers([agentKeypair]).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        // Neither can the manager while the admin operations are paused
+        await program.methods.pauseOperations(PAUSE_ADMIN, 0, new anchor.BN(0)).accounts({
+            pauser: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+        try {
+            await setEmissionSchedule(mint, 0)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ShouldNotBePaused")
+        }
+
+        await program.methods.unpauseOperations(PAUSE_ADMIN).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+        await setEmissionSchedule(mint, 0)
+        const rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.isTrue(rewardsAccount.emissionSchedule.mint.equals(mint))
+    })
+});
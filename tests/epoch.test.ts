@@ -158,7 +158,14 @@ describe("epoch tests", () => {
       epochNr: current_epoch_nr,
     });
 
-    const argsTuple: [number, number[]] = [currentEpochBump, root];
+    const argsTuple: [number, number[], anchor.BN, anchor.BN, string, number[]] = [
+      currentEpochBump,
+      root,
+      new anchor.BN(10),
+      new anchor.BN(1),
+      "",
+      new Array(32).fill(0),
+    ];
 
     const mintAccount = await createNewMint();
 
@@ -199,7 +206,14 @@ describe("epoch tests", () => {
       epochNr: current_epoch_nr,
     });
 
-    const argsTuple: [number, number[]] = [currentEpochBump, root];
+    const argsTuple: [number, number[], anchor.BN, anchor.BN, string, number[]] = [
+      currentEpochBump,
+      root,
+      new anchor.BN(10),
+      new anchor.BN(1),
+      "",
+      new Array(32).fill(0),
+    ];
 
     await program.methods
       .addEpoch(...argsTuple)
@@ -228,7 +242,7 @@ describe("epoch tests", () => {
 
     try {
       await program.methods
-        .correctEpoch(current_epoch_nr, corrected_root)
+        .correctEpoch(current_epoch_nr, corrected_root, new anchor.BN(10), new anchor.BN(1), "", new Array(32).fill(0))
         .accounts({
           agent: manager.publicKey,
           mint,
@@ -260,7 +274,7 @@ describe("epoch tests", () => {
     });
 
     await program.methods
-      .correctEpoch(current_epoch_nr, corrected_root)
+      .correctEpoch(current_epoch_nr, corrected_root, new anchor.BN(10), new anchor.BN(1), "", new Array(32).fill(0))
       .accounts({
         agent: agent.publicKey,
         mint,
@@ -286,7 +300,14 @@ describe("epoch tests", () => {
       epochNr: current_epoch_nr,
     });
 
-    const argsTuple: [number, number[]] = [currentEpochBump, root];
+    const argsTuple: [number, number[], anchor.BN, anchor.BN, string, number[]] = [
+      currentEpochBump,
+      root,
+      new anchor.BN(10),
+      new anchor.BN(1),
+      "",
+      new Array(32).fill(0),
+    ];
 
     try {
       await program.methods
@@ -346,7 +367,12 @@ describe("epoch tests", () => {
 
     try {
       await program.methods
-        .approveEpoch(current_epoch_nr, new anchor.BN(10))
+        .approveEpoch(
+          current_epoch_nr,
+          new anchor.BN(10),
+          epochAccountBeforeCall.hash,
+          epochAccountBeforeCall.correctionCount
+        )
         .accounts({
           rewardsAccount: rewardsAccountKeypair.publicKey,
           epochAccount: currentEpoch,
@@ -389,7 +415,12 @@ describe("epoch tests", () => {
     );
 
     await program.methods
-      .approveEpoch(current_epoch_nr, new anchor.BN(10))
+      .approveEpoch(
+          current_epoch_nr,
+          new anchor.BN(10),
+          epochAccountBeforeCall.hash,
+          epochAccountBeforeCall.correctionCount
+        )
       .accounts({
         rewardsAccount: rewardsAccountKeypair.publicKey,
         epochAccount: currentEpoch,
@@ -420,7 +451,7 @@ describe("epoch tests", () => {
     );
     const epoch_nr = rewardAccountBeforeCall.currentApprovedEpoch;
 
-    const argsTuple: [u64, u64, number[][]] = [index, amount, proof];
+    const argsTuple: [u64, u64, number[][], number] = [index, amount, proof, 0];
 
     const wrongMint = await createMint(
       provider.connection,
@@ -494,7 +525,7 @@ describe("epoch tests", () => {
     );
     const epoch_nr = rewardAccountBeforeCall.currentApprovedEpoch;
 
-    const argsTuple: [u64, u64, number[][]] = [index, amount, proof];
+    const argsTuple: [u64, u64, number[][], number] = [index, amount, proof, 0];
     const receiverTokenAccount = await getAssociatedTokenAddress(
       mint,
       receiver.publicKey,
@@ -562,7 +593,7 @@ describe("epoch tests", () => {
       program: program.programId,
     });
 
-    const argsTuple: [u64, u64, number[][]] = [index, amount, []];
+    const argsTuple: [u64, u64, number[][], number] = [index, amount, [], 0];
 
     const fakeReceiverTokenAccount = await getAssociatedTokenAddress(
       mint,
@@ -613,9 +644,13 @@ describe("epoch tests", () => {
       epochNr: current_epoch_nr,
     });
 
-    const argsTuple: [number, number[]] = [
+    const argsTuple: [number, number[], anchor.BN, anchor.BN, string, number[]] = [
       currentEpochBump,
       epochTwoCorrected_root,
+      new anchor.BN(10),
+      new anchor.BN(1),
+      "",
+      new Array(32).fill(0),
     ];
 
     await program.methods
@@ -655,7 +690,12 @@ describe("epoch tests", () => {
     );
 
     await program.methods
-      .approveEpoch(current_epoch_nr, new anchor.BN(10))
+      .approveEpoch(
+          current_epoch_nr,
+          new anchor.BN(10),
+          epochAccountBeforeCall.hash,
+          epochAccountBeforeCall.correctionCount
+        )
       .accounts({
         rewardsAccount: rewardsAccountKeypair.publicKey,
         epochAccount: currentEpoch,
@@ -0,0 +1,204 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA} from "../src/utils/pda";
+import {beforeEach} from "mocha";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+} = require("@solana/spl-token");
+
+describe("epoch metadata tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Airdrops
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        rewardsAccountKeypair = Keypair.generate();
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should store the distribution file of the epoch", async () => {
+        const treeRoot = Array(32).fill(1)
+        const amount = new anchor.BN(10)
+
+        // Add epoch 1 with its distribution file
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        const metadataUri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        const distributionHash = Array(32).fill(2)
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), metadataUri, distributionHash).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(epochAccount.metadataUri, metadataUri)
+        assert.deepEqual(epochAccount.distributionHash, distributionHash)
+        const epochAccountInfo = await provider.connection.getAccountInfo(epoch1)
+
+        // The account is resized to fit the corrected URI
+        const correctEpoch = async (metadataUri: string) => {
+            await program.methods.correctEpoch(epochNr, treeRoot, amount, new anchor.BN(1), metadataUri, Array(32).fill(3)).accounts({
+                agent: agentKeypair.publicKey,
+                mint,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                systemProgram: SystemProgram.programId,
+            }).signers([agentKeypair]).rpc();
+        }
+        const correctedMetadataUri = metadataUri + "/epoch-1.json"
+        await correctEpoch(correctedMetadataUri)
+
+        const correctedEpochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(correctedEpochAccount.metadataUri, correctedMetadataUri)
+        assert.deepEqual(correctedEpochAccount.distributionHash, Array(32).fill(3))
+        const correctedEpochAccountInfo = await provider.connection.getAccountInfo(epoch1)
+        assert.equal(
+            correctedEpochAccountInfo.data.length - epochAccountInfo.data.length,
+            correctedMetadataUri.length - metadataUri.length
+        )
+
+        // The URI is bounded
+        try {
+            await correctEpoch("ipfs://" + "a".repeat(200))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "MetadataUriTooLong")
+        }
+    })
+
+    it("should only let the agent add and correct an epoch with a bounded URI", async () => {
+        const treeRoot = Array(32).fill(1)
+        const amount = new anchor.BN(10)
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        const addEpoch = async (metadataUri: string) => {
+            await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), metadataUri, Array(32).fill(2)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                currentEpochAccount: epoch1,
+                mint,
+                agent: agentKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([agentKeypair]).rpc();
+        }
+
+        // The URI is bounded when adding the epoch too
+        try {
+            await addEpoch("ipfs://" + "a".repeat(200))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "MetadataUriTooLong")
+        }
+
+        await addEpoch("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi")
+
+        // The manager cannot correct the epoch
+        try {
+            await program.methods.correctEpoch(epochNr, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(3)).accounts({
+                agent: managerKeypair.publicKey,
+                mint,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+    })
+
+    it("should grow the epoch account with its added mints", async () => {
+        // Add epoch 1 without a distribution file
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, Array(32).fill(1), new anchor.BN(10), new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        const epochAccountInfo = await provider.connection.getAccountInfo(epoch1)
+
+        // Adding a mint grows the account by one entry, the agent paying the rent
+        const bonusMint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+        await program.methods.addEpochMint(epochNr).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            mint: bonusMint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        const grownEpochAccountInfo = await provider.connection.getAccountInfo(epoch1)
+        // Mint, funded amount, claimed amount and swept flag
+        assert.equal(grownEpochAccountInfo.data.length - epochAccountInfo.data.length, 32 + 8 + 8 + 1)
+        assert.isAbove(grownEpochAccountInfo.lamports, epochAccountInfo.lamports)
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(epochAccount.bonusMints[0].mint.equals(bonusMint))
+    })
+});
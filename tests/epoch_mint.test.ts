@@ -0,0 +1,253 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("epoch mint tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    // Mints
+    let mint: PublicKey
+    let bonusMint: PublicKey
+
+    // Creates a mint and mints to the manager ATA
+    const createFundedMint = async (): Promise<PublicKey> => {
+        const newMint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+        const managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            newMint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            newMint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+        return newMint
+    }
+
+    // Create the mints and airdrop to each member involved
+    before("create mints and do airdrops", async () => {
+        mint = await createFundedMint()
+        bonusMint = await createFundedMint()
+
+        // Airdrops
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+        await confirmedAirdrop(
+            provider.connection,
+            receiverKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should claim the primary and the added mint of an epoch", async () => {
+        // Tree, with one leaf per mint
+        const amount = new anchor.BN(10)
+        const bonusAmount = new anchor.BN(25)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+            {
+                account: receiverKeypair.publicKey,
+                amount: bonusAmount,
+                mintIndex: 1,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+
+        // Add epoch 1 and its bonus mint
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(2), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        await program.methods.addEpochMint(epochNr).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            mint: bonusMint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        // The same mint cannot be added twice
+        try {
+            await program.methods.addEpochMint(epochNr).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                mint: bonusMint,
+                agent: agentKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([agentKeypair]).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "DuplicateEpochMint")
+        }
+
+        // The bonus mint is funded once the epoch is approved
+        const fundEpochMint = async (fundAmount: anchor.BN) => {
+            await program.methods.fundEpochMint(epochNr, 1, fundAmount).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(bonusMint, epoch1, true),
+                managerTokenAccount: await getAssociatedTokenAddress(bonusMint, managerKeypair.publicKey),
+                mintAccount: bonusMint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+        try {
+            await fundEpochMint(bonusAmount)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochShouldBeApproved")
+        }
+
+        const approveEpoch = async (correctionCount: anchor.BN) => {
+            await program.methods.approveEpoch(epochNr, amount, treeRoot, correctionCount).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+                managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+
+        // Adding the mint counts as a correction, an approval reviewed before it fails
+        try {
+            await approveEpoch(new anchor.BN(0))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochChangedSinceReview")
+        }
+
+        // Approve the epoch and fund the bonus mint short of its leaf
+        await approveEpoch(new anchor.BN(1))
+        await fundEpochMint(bonusAmount.subn(5))
+
+        // Claim both leaves, each from the ATA of its mint
+        const claim = async (index: number, leafAmount: anchor.BN, mintIndex: number, claimMint: PublicKey) => {
+            const leafIndex = new anchor.BN(index)
+            const proof = tree.getProof(index, receiverKeypair.publicKey, leafAmount, mintIndex).map((e) =>
+                Array.from(Uint8Array.from(e))
+            )
+            await program.methods.claim(leafIndex, leafAmount, proof, mintIndex).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimStatus: findClaimStatusKey({
+                    rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                    index: leafIndex,
+                    epochAccount: epoch1,
+                    program: program.programId
+                })[0],
+                from: await getAssociatedTokenAddress(claimMint, epoch1, true),
+                to: await getAssociatedTokenAddress(claimMint, receiverKeypair.publicKey),
+                receiver: receiverKeypair.publicKey,
+                mintAccount: claimMint,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).signers([receiverKeypair]).rpc();
+        }
+
+        // The bonus leaf does not pay the primary mint
+        try {
+            await claim(1, bonusAmount, 1, mint)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "InvalidMintAccount")
+        }
+
+        // The bonus leaf cannot be paid beyond the funded amount of its mint
+        try {
+            await claim(1, bonusAmount, 1, bonusMint)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "FundedAmountExceeded")
+        }
+
+        await fundEpochMint(new anchor.BN(5))
+        await claim(0, amount, 0, mint)
+        await claim(1, bonusAmount, 1, bonusMint)
+
+        const receiverBonus = await getAccount(
+            provider.connection,
+            await getAssociatedTokenAddress(bonusMint, receiverKeypair.publicKey)
+        )
+        assert.equal(Number(receiverBonus.amount), bonusAmount.toNumber())
+
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(epochAccount.totalAmountClaimed.toNumber(), amount.toNumber())
+        assert.equal(epochAccount.numNodesClaimed.toNumber(), 2)
+        assert.equal(epochAccount.bonusMints.length, 1)
+        assert.isTrue(epochAccount.bonusMints[0].mint.equals(bonusMint))
+        assert.equal(epochAccount.bonusMints[0].fundedAmount.toNumber(), bonusAmount.toNumber())
+        assert.equal(epochAccount.bonusMints[0].totalAmountClaimed.toNumber(), bonusAmount.toNumber())
+    })
+});
@@ -0,0 +1,203 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("epoch totals tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+    const otherReceiverKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint, with decimals to check that amounts are in base units
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            6 // Decimals
+        );
+
+        // Create the manager ATA and mint to it
+        const managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Airdrops
+        for (const keypair of [agentKeypair, receiverKeypair, otherReceiverKeypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        rewardsAccountKeypair = Keypair.generate();
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    const addEpoch = async (tree: BalanceTree, maxTotalClaim: anchor.BN, maxNumNodes: anchor.BN): Promise<[PublicKey, number[]]> => {
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr: new anchor.BN(1),
+        });
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+        await program.methods.addEpoch(epoch1Bump, treeRoot, maxTotalClaim, maxNumNodes, "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        return [epoch1, treeRoot]
+    }
+
+    const approveEpoch = async (epoch: PublicKey, amount: anchor.BN, treeRoot: number[]) => {
+        await program.methods.approveEpoch(new anchor.BN(1), amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch, true),
+            managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+    }
+
+    it("should fund exactly the declared total", async () => {
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const [epoch1, treeRoot] = await addEpoch(tree, amount, new anchor.BN(1))
+
+        // The manager must approve the declared total
+        try {
+            await approveEpoch(epoch1, new anchor.BN(11), treeRoot)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochTotalMismatch")
+        }
+
+        await approveEpoch(epoch1, amount, treeRoot)
+
+        // The amount is in base units, regardless of the decimals of the mint
+        const epochTokenAccount = await getAccount(
+            provider.connection,
+            await getAssociatedTokenAddress(mint, epoch1, true)
+        )
+        assert.equal(Number(epochTokenAccount.amount), amount.toNumber())
+    })
+
+    it("should not claim past the declared total", async () => {
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+            {
+                account: otherReceiverKeypair.publicKey,
+                amount,
+            },
+        ])
+
+        // The tree owes 20 but the agent declared 15
+        const maxTotalClaim = new anchor.BN(15)
+        const [epoch1, treeRoot] = await addEpoch(tree, maxTotalClaim, new anchor.BN(2))
+        await approveEpoch(epoch1, maxTotalClaim, treeRoot)
+
+        const claim = async (index: number, receiver: Keypair) => {
+            const leafIndex = new anchor.BN(index)
+            const proof = tree.getProof(index, receiver.publicKey, amount).map((e) =>
+                Array.from(Uint8Array.from(e))
+            )
+            await program.methods.claim(leafIndex, amount, proof, 0).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimStatus: findClaimStatusKey({
+                    rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                    index: leafIndex,
+                    epochAccount: epoch1,
+                    program: program.programId
+                })[0],
+                from: await getAssociatedTokenAddress(mint, epoch1, true),
+                to: await getAssociatedTokenAddress(mint, receiver.publicKey),
+                receiver: receiver.publicKey,
+                mintAccount: mint,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).signers([receiver]).rpc();
+        }
+
+        await claim(0, receiverKeypair)
+
+        try {
+            await claim(1, otherReceiverKeypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "MaxTotalClaimExceeded")
+        }
+
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(epochAccount.totalAmountClaimed.toNumber(), amount.toNumber())
+        assert.equal(epochAccount.numNodesClaimed.toNumber(), 1)
+    })
+});
@@ -0,0 +1,222 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+// Pause bit of the approvals
+const PAUSE_APPROVALS = 4
+
+describe("fund epoch tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const funderKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+    let funderTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA and mint to it
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Create the funder ATA and mint to it
+        funderTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            funderKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            funderTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Airdrops
+        for (const keypair of [agentKeypair, funderKeypair, receiverKeypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should only top up an approved epoch up to its shortfall", async () => {
+        await program.methods.setFunder(funderKeypair.publicKey).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        // Tree
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+
+        // Add epoch 1
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        const epochTokenAccount = await getAssociatedTokenAddress(mint, epoch1, true)
+
+        const fundEpoch = async (funder: Keypair, funderTokenAccount: PublicKey, amount: anchor.BN) => {
+            await program.methods.fundEpoch(epochNr, amount).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                funder: funder.publicKey,
+                epochTokenAccount,
+                funderTokenAccount,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).signers([funder]).rpc();
+        }
+
+        // Only an approved epoch can be topped up
+        try {
+            await fundEpoch(funderKeypair, funderTokenAccount, new anchor.BN(5))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochShouldBeApproved")
+        }
+
+        // Approve epoch 1
+        await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount,
+            managerTokenAccount,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+        const approvedEpochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(approvedEpochAccount.fundedAmount.toNumber(), amount.toNumber())
+
+        // Only the manager or the funder can top up
+        const receiverTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            receiverKeypair.publicKey // Owner
+        );
+        try {
+            await fundEpoch(receiverKeypair, receiverTokenAccount, new anchor.BN(5))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        // Top-ups are paused with the approvals
+        await program.methods.pauseOperations(PAUSE_APPROVALS, 0, new anchor.BN(0)).accounts({
+            pauser: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+        try {
+            await fundEpoch(funderKeypair, funderTokenAccount, new anchor.BN(1))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ShouldNotBePaused")
+        }
+        await program.methods.unpauseOperations(PAUSE_APPROVALS).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        // The vault holds the declared total, a top-up could never be claimed
+        try {
+            await fundEpoch(funderKeypair, funderTokenAccount, new anchor.BN(1))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochShortfallExceeded")
+        }
+
+        const fundedEpochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(fundedEpochAccount.fundedAmount.toNumber(), amount.toNumber())
+        assert.equal(fundedEpochAccount.topUps.length, 0)
+        assert.equal(Number((await getAccount(provider.connection, epochTokenAccount)).amount), amount.toNumber())
+        assert.equal(Number((await getAccount(provider.connection, funderTokenAccount)).amount), 10_000)
+    })
+});
@@ -0,0 +1,355 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, deriveManagerProposalPDA, deriveRoleGrantPDA} from "../src/utils/pda";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("manager multisig tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const member2Keypair = Keypair.generate();
+    const member3Keypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA and mint to it
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Airdrops
+        for (const keypair of [agentKeypair, member2Keypair, member3Keypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        rewardsAccountKeypair = Keypair.generate();
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should execute sensitive actions through proposals once they reach the threshold", async () => {
+        // 2-of-3 multisig
+        await program.methods.setManagerMultisig([
+            managerKeypair.publicKey,
+            member2Keypair.publicKey,
+            member3Keypair.publicKey,
+        ], 2).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        // The manager alone can no longer change the agent
+        const newAgentKeypair = Keypair.generate();
+        try {
+            await program.methods.changeAgent(newAgentKeypair.publicKey).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ProposalRequired")
+        }
+
+        // Member 2 proposes the change, member 3 approves and executes it
+        const [proposal1] = deriveManagerProposalPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            proposalNr: new anchor.BN(1),
+        });
+        await program.methods.createProposal({changeAgent: {newAgent: newAgentKeypair.publicKey}}).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            proposal: proposal1,
+            member: member2Keypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([member2Keypair]).rpc();
+        let rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.agent.toBase58(), agentKeypair.publicKey.toBase58())
+
+        await program.methods.approveProposal(new anchor.BN(1)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            proposal: proposal1,
+            member: member3Keypair.publicKey,
+        }).signers([member3Keypair]).rpc();
+        rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.agent.toBase58(), newAgentKeypair.publicKey.toBase58())
+        const proposal1Account = await program.account.managerProposal.fetch(proposal1)
+        assert.isTrue(proposal1Account.isExecuted)
+
+        // Approving an epoch needs a proposal approved by the threshold
+        await confirmedAirdrop(
+            provider.connection,
+            newAgentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+        const epochNr = new anchor.BN(1)
+        const amount = new anchor.BN(10)
+        const treeRoot = Array(32).fill(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: newAgentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([newAgentKeypair]).rpc();
+
+        const [proposal2] = deriveManagerProposalPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            proposalNr: new anchor.BN(2),
+        });
+        const approveEpoch = async (proposal: PublicKey | null) => {
+            await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                proposal,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+                managerTokenAccount,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+        try {
+            await approveEpoch(null)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ProposalRequired")
+        }
+
+        await program.methods.createProposal({
+            approveEpoch: {
+                epochNr,
+                amount,
+                expectedRoot: treeRoot,
+                expectedCorrectionCount: new anchor.BN(0),
+                kind: {token: {}},
+                fundingSource: {approver: {}},
+            },
+        }).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            proposal: proposal2,
+            member: member2Keypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([member2Keypair]).rpc();
+
+        // The approval of the manager, a member, reaches the threshold and executes it
+        await approveEpoch(proposal2)
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(epochAccount.isApproved)
+        const proposal2Account = await program.account.managerProposal.fetch(proposal2)
+        assert.isTrue(proposal2Account.isExecuted)
+        assert.equal(proposal2Account.approvers.length, 2)
+
+        // Granting a role also needs a proposal, executed by the grant instruction
+        const approverKeypair = Keypair.generate();
+        const [roleGrant] = deriveRoleGrantPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            role: 1,
+            member: approverKeypair.publicKey,
+        });
+        const grantRole = async (proposal: PublicKey | null) => {
+            await program.methods.grantRole({approver: {}}, approverKeypair.publicKey).accounts({
+                manager: managerKeypair.publicKey,
+                proposal,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                roleGrant,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+        try {
+            await grantRole(null)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ProposalRequired")
+        }
+
+        const [proposal3] = deriveManagerProposalPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            proposalNr: new anchor.BN(3),
+        });
+        await program.methods.createProposal({
+            grantRole: {role: {approver: {}}, member: approverKeypair.publicKey},
+        }).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            proposal: proposal3,
+            member: member2Keypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([member2Keypair]).rpc();
+
+        // A proposal of another action does not grant the role
+        try {
+            await grantRole(proposal2)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ProposalMismatch")
+        }
+
+        await grantRole(proposal3)
+        const roleGrantAccount = await program.account.roleGrant.fetch(roleGrant)
+        assert.isTrue(roleGrantAccount.member.equals(approverKeypair.publicKey))
+        const proposal3Account = await program.account.managerProposal.fetch(proposal3)
+        assert.isTrue(proposal3Account.isExecuted)
+    })
+
+    it("should reject invalid multisigs, outsiders and proposals that cannot execute", async () => {
+        const setManagerMultisig = async (members: PublicKey[], threshold: number) => {
+            await program.methods.setManagerMultisig(members, threshold).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+        }
+
+        // Members must be unique, and the threshold reachable
+        for (const [members, threshold] of [
+            [[managerKeypair.publicKey, member2Keypair.publicKey, managerKeypair.publicKey], 2],
+            [[managerKeypair.publicKey, member2Keypair.publicKey], 3],
+            [[managerKeypair.publicKey, member2Keypair.publicKey], 0],
+        ] as [PublicKey[], number][]) {
+            try {
+                await setManagerMultisig(members, threshold)
+                assert(false, "should've failed but didn't");
+            } catch (_err) {
+                assert.equal(_err.error.errorCode.code, "InvalidMultisig")
+            }
+        }
+
+        // 3-of-3 multisig
+        await setManagerMultisig([
+            managerKeypair.publicKey,
+            member2Keypair.publicKey,
+            member3Keypair.publicKey,
+        ], 3)
+
+        // Only members create proposals
+        const newAgentKeypair = Keypair.generate();
+        const [proposal1] = deriveManagerProposalPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            proposalNr: new anchor.BN(1),
+        });
+        const createProposal = async (action: any, proposal: PublicKey, member: Keypair) => {
+            await program.methods.createProposal(action).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                proposal,
+                member: member.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([member]).rpc();
+        }
+        try {
+            await createProposal({changeAgent: {newAgent: newAgentKeypair.publicKey}}, proposal1, agentKeypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        // The proposal executes with the last approval, and only once
+        await createProposal({changeAgent: {newAgent: newAgentKeypair.publicKey}}, proposal1, member2Keypair)
+        const approveProposal = async (member: Keypair) => {
+            await program.methods.approveProposal(new anchor.BN(1)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                proposal: proposal1,
+                member: member.publicKey,
+            }).signers([member]).rpc();
+        }
+        await approveProposal(member3Keypair)
+        let rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.agent.toBase58(), agentKeypair.publicKey.toBase58())
+        await approveProposal(managerKeypair)
+        rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.agent.toBase58(), newAgentKeypair.publicKey.toBase58())
+        try {
+            await approveProposal(member3Keypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ProposalAlreadyExecuted")
+        }
+
+        // A grant approved by two of the three members does not execute
+        const granteeKeypair = Keypair.generate();
+        const [proposal2] = deriveManagerProposalPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            proposalNr: new anchor.BN(2),
+        });
+        await createProposal({grantRole: {role: {pauser: {}}, member: granteeKeypair.publicKey}}, proposal2, member2Keypair)
+        const [roleGrant] = deriveRoleGrantPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            role: 2,
+            member: granteeKeypair.publicKey,
+        });
+        try {
+            await program.methods.grantRole({pauser: {}}, granteeKeypair.publicKey).accounts({
+                manager: managerKeypair.publicKey,
+                proposal: proposal2,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                roleGrant,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ProposalThresholdNotReached")
+        }
+    })
+});
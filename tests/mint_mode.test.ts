@@ -0,0 +1,305 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, deriveMintAuthorityPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    getAccount,
+    getMint,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("mint mode tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+    const otherReceiverKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint, the manager holds its mint authority until handing it to the program
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            managerKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Airdrops
+        for (const keypair of [agentKeypair, receiverKeypair, otherReceiverKeypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        rewardsAccountKeypair = Keypair.generate();
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should mint claims up to the emission cap", async () => {
+        const [mintAuthority] = deriveMintAuthorityPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+        })
+
+        // Hand the mint authority to the program and cap the emissions
+        await program.methods.acceptMintAuthority().accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            manager: managerKeypair.publicKey,
+            mintAuthority,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).rpc();
+        assert.isTrue((await getMint(provider.connection, mint)).mintAuthority.equals(mintAuthority))
+
+        const setEmissionCap = async (emissionCap: number) => {
+            await program.methods.setEmissionCap(new anchor.BN(emissionCap)).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+        }
+        await setEmissionCap(15)
+
+        // Tree
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+            {
+                account: otherReceiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+        const maxTotalClaim = new anchor.BN(20)
+
+        // Add and approve epoch 1 in mint mode
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, maxTotalClaim, new anchor.BN(2), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        const epochTokenAccount = await getAssociatedTokenAddress(mint, epoch1, true)
+        await program.methods.approveMintEpoch(epochNr, maxTotalClaim, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount,
+            mintAuthority,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(epochAccount.isMintMode)
+
+        const claim = async (index: number, receiver: Keypair) => {
+            const leafIndex = new anchor.BN(index)
+            const proof = tree.getProof(index, receiver.publicKey, amount).map((e) =>
+                Array.from(Uint8Array.from(e))
+            )
+            await program.methods.claim(leafIndex, amount, proof, 0).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimStatus: findClaimStatusKey({
+                    rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                    index: leafIndex,
+                    epochAccount: epoch1,
+                    program: program.programId
+                })[0],
+                from: epochTokenAccount,
+                to: await getAssociatedTokenAddress(mint, receiver.publicKey),
+                receiver: receiver.publicKey,
+                mintAccount: mint,
+                mintAuthority,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).signers([receiver]).rpc();
+        }
+
+        // The claim is minted to the receiver
+        await claim(0, receiverKeypair)
+        const receiverTokenAccount = await getAccount(
+            provider.connection,
+            await getAssociatedTokenAddress(mint, receiverKeypair.publicKey)
+        )
+        assert.equal(Number(receiverTokenAccount.amount), amount.toNumber())
+
+        // The second claim would mint past the emission cap
+        try {
+            await claim(1, otherReceiverKeypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EmissionCapExceeded")
+        }
+
+        // The epoch ATA is empty but a leaf is unclaimed, so the epoch cannot be closed yet
+        try {
+            await program.methods.closeEpoch(epochNr).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount,
+                creator: agentKeypair.publicKey,
+                approver: managerKeypair.publicKey,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochNotSettled")
+        }
+
+        await setEmissionCap(20)
+        await claim(1, otherReceiverKeypair)
+        const rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.totalMinted.toNumber(), 20)
+
+        // Hand the mint authority back to the manager
+        await program.methods.releaseMintAuthority(managerKeypair.publicKey).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            manager: managerKeypair.publicKey,
+            mintAuthority,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).rpc();
+        assert.isTrue((await getMint(provider.connection, mint)).mintAuthority.equals(managerKeypair.publicKey))
+    })
+
+    it("should only mint with the mint authority handed to the program", async () => {
+        const [mintAuthority] = deriveMintAuthorityPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+        })
+
+        // Add epoch 1
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        // The program cannot mint before it holds the mint authority
+        try {
+            await program.methods.approveMintEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+                mintAuthority,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "InvalidMintAuthority")
+        }
+
+        // Only the mint authority held by the manager can be handed over
+        const otherMint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            agentKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+        try {
+            await program.methods.acceptMintAuthority().accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                manager: managerKeypair.publicKey,
+                mintAuthority,
+                mintAccount: otherMint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "InvalidMintAuthority")
+        }
+
+        // Once handed over, only the manager can release the mint authority
+        await program.methods.acceptMintAuthority().accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            manager: managerKeypair.publicKey,
+            mintAuthority,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).rpc();
+        const releaseMintAuthority = async (manager: Keypair) => {
+            await program.methods.releaseMintAuthority(manager.publicKey).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                manager: manager.publicKey,
+                mintAuthority,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).signers([manager]).rpc();
+        }
+        try {
+            await releaseMintAuthority(agentKeypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        await releaseMintAuthority(managerKeypair)
+        assert.isTrue((await getMint(provider.connection, mint)).mintAuthority.equals(managerKeypair.publicKey))
+    })
+});
@@ -0,0 +1,150 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA} from "../src/utils/pda";
+import {beforeEach} from "mocha";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+// Pause bits
+const PAUSE_SUBMISSIONS = 2
+const PAUSE_APPROVALS = 4
+const PAUSE_ALL = 15
+
+describe("pause operations tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const guardianKeypair = Keypair.generate();
+
+    before("do airdrops", async () => {
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should let the guardian pause submissions only, until the pause expires", async () => {
+        await program.methods.setGuardian(guardianKeypair.publicKey).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        const pauseOperations = async (flags: number, reason: number, expiresAt: number) => {
+            await program.methods.pauseOperations(flags, reason, new anchor.BN(expiresAt)).accounts({
+                pauser: guardianKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).signers([guardianKeypair]).rpc();
+        }
+
+        // The guardian pauses the submissions for a few seconds
+        let events = [];
+        const listener = program.addEventListener("Paused", (event: any) => {
+            events.push(event);
+        });
+        await pauseOperations(PAUSE_SUBMISSIONS, 7, Math.floor(Date.now() / 1000) + 3)
+        await new Promise((resolve) => setTimeout(resolve, 1000));
+        program.removeEventListener(listener);
+        assert.equal(events.length, 1)
+        assert.equal(events[0].pauseFlags, PAUSE_SUBMISSIONS)
+        assert.equal(events[0].reason, 7)
+
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        const addEpoch = async () => {
+            await program.methods.addEpoch(epoch1Bump, Array(32).fill(1), new anchor.BN(10), new anchor.BN(1), "", Array(32).fill(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                currentEpochAccount: epoch1,
+                mint: null,
+                agent: agentKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([agentKeypair]).rpc();
+        }
+        try {
+            await addEpoch()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ShouldNotBePaused")
+        }
+
+        // The configuration can still be changed
+        await program.methods.setClaimWindow(new anchor.BN(60)).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        // The guardian cannot unpause
+        try {
+            await program.methods.unpauseOperations(PAUSE_SUBMISSIONS).accounts({
+                manager: guardianKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).signers([guardianKeypair]).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        // Once the pause expires, submissions resume
+        await new Promise((resolve) => setTimeout(resolve, 4000));
+        await addEpoch()
+
+        // Pausing everything still pauses what approvals paused without expiry left running
+        await pauseOperations(PAUSE_APPROVALS, 0, 0)
+        const pause = async () => {
+            await program.methods.pause().accounts({
+                manager: guardianKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).signers([guardianKeypair]).rpc();
+        }
+        await pause()
+        let rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.pauseFlags, PAUSE_ALL)
+        try {
+            await pause()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ShouldNotBePaused")
+        }
+
+        // The manager resumes everything but approvals, then approvals
+        await program.methods.unpauseOperations(PAUSE_ALL & ~PAUSE_APPROVALS).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+        await program.methods.unpauseOperations(PAUSE_APPROVALS).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+        rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.pauseFlags, 0)
+    })
+});
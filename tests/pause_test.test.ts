@@ -85,7 +85,7 @@ describe("pause instruction", () => {
     const rewardAccount = await program.account.rewardsAccount.fetch(
       rewardsAccountKeypair.publicKey
     );
-    assert(rewardAccount.isPaused, "Paused boolean should be true");
+    assert.equal(rewardAccount.pauseFlags, 15, "All pause bits should be set");
   });
 
   it("authorized manager cannot pause program when program is already paused", async () => {
@@ -0,0 +1,133 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA} from "../src/utils/pda";
+import {beforeEach} from "mocha";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+describe("propose agent tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const newAgentKeypair = Keypair.generate();
+    const mistypedAgentKeypair = Keypair.generate();
+
+    before("do airdrops", async () => {
+        for (const keypair of [agentKeypair, newAgentKeypair, mistypedAgentKeypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        rewardsAccountKeypair = Keypair.generate();
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should rotate the agent in two steps with an overlap period", async () => {
+        const proposeAgent = async (proposedAgent: PublicKey, overlap: number) => {
+            await program.methods.proposeAgent(proposedAgent, new anchor.BN(overlap)).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+        }
+        const acceptAgent = async (keypair: Keypair) => {
+            await program.methods.acceptAgent().accounts({
+                proposedAgent: keypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).signers([keypair]).rpc();
+        }
+        const cancelProposedAgent = async () => {
+            await program.methods.cancelProposedAgent().accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+        }
+
+        // The manager cancels a mistyped proposal, which can no longer be accepted
+        await proposeAgent(mistypedAgentKeypair.publicKey, 0)
+        await cancelProposedAgent()
+        try {
+            await acceptAgent(mistypedAgentKeypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+        try {
+            await cancelProposedAgent()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "NoPendingProposal")
+        }
+
+        // The new agent accepts, the previous one keeps submitting for a minute
+        await proposeAgent(newAgentKeypair.publicKey, 60)
+        await acceptAgent(newAgentKeypair)
+        const rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.agent.toBase58(), newAgentKeypair.publicKey.toBase58())
+        assert.equal(rewardsAccount.previousAgent.toBase58(), agentKeypair.publicKey.toBase58())
+        assert.equal(rewardsAccount.proposedAgent.toBase58(), PublicKey.default.toBase58())
+
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, Array(32).fill(1), new anchor.BN(10), new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint: null,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        await program.methods.correctEpoch(epochNr, Array(32).fill(2), new anchor.BN(10), new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            agent: newAgentKeypair.publicKey,
+            mint: null,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+        }).signers([newAgentKeypair]).rpc();
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(epochAccount.correctionCount.toNumber(), 1)
+    })
+
+    it("should let the manager cancel a proposed manager", async () => {
+        const proposedManagerKeypair = Keypair.generate();
+        await program.methods.proposeManager(proposedManagerKeypair.publicKey).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+        await program.methods.cancelProposedManager().accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        const rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.proposedManager.toBase58(), PublicKey.default.toBase58())
+    })
+});
@@ -0,0 +1,175 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("reject epoch tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA and mint to it
+        const managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Airdrops
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should require a resubmission after a rejection", async () => {
+        // Tree
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+
+        // Add epoch 1
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        // Only the manager can reject
+        const rejectEpoch = async (signer: Keypair) => {
+            await program.methods.rejectEpoch(epochNr, 3).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: signer.publicKey,
+            }).signers([signer]).rpc();
+        }
+        try {
+            await rejectEpoch(agentKeypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        await rejectEpoch(managerKeypair)
+        const rejectedEpochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(rejectedEpochAccount.isRejected)
+        assert.equal(rejectedEpochAccount.rejectionReason, 3)
+        assert.equal(rejectedEpochAccount.rejectionCount.toNumber(), 1)
+
+        // The epoch cannot be rejected again nor approved until it is resubmitted
+        try {
+            await rejectEpoch(managerKeypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochRejected")
+        }
+
+        const approveEpoch = async (correctionCount: anchor.BN) => {
+            await program.methods.approveEpoch(epochNr, amount, treeRoot, correctionCount).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+                managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+        try {
+            await approveEpoch(new anchor.BN(0))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochRejected")
+        }
+
+        // Resubmit and approve
+        await program.methods.correctEpoch(epochNr, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            agent: agentKeypair.publicKey,
+            mint,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+        }).signers([agentKeypair]).rpc();
+        const resubmittedEpochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isFalse(resubmittedEpochAccount.isRejected)
+        assert.equal(resubmittedEpochAccount.rejectionCount.toNumber(), 1)
+
+        await approveEpoch(resubmittedEpochAccount.correctionCount)
+        const approvedEpochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(approvedEpochAccount.isApproved)
+    })
+});
@@ -0,0 +1,241 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, deriveRoleGrantPDA, deriveTimelockOperationPDA, findClaimBitmapKey} from "../src/utils/pda";
+import {beforeEach} from "mocha";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+} = require("@solana/spl-token");
+
+describe("roles tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const pauserKeypair = Keypair.generate();
+    const configAdminKeypair = Keypair.generate();
+
+    before("do airdrops", async () => {
+        for (const keypair of [agentKeypair, pauserKeypair, configAdminKeypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        rewardsAccountKeypair = Keypair.generate();
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should let a pauser pause without any other power", async () => {
+        const [roleGrant] = deriveRoleGrantPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            role: 2,
+            member: pauserKeypair.publicKey,
+        });
+        await program.methods.grantRole({pauser: {}}, pauserKeypair.publicKey).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            roleGrant,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+
+        // The pauser cannot change the configuration
+        try {
+            await program.methods.setClaimWindow(new anchor.BN(1)).accounts({
+                manager: pauserKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                roleGrant,
+            }).signers([pauserKeypair]).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        // But can pause
+        const pause = async () => {
+            await program.methods.pause().accounts({
+                manager: pauserKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                roleGrant,
+            }).signers([pauserKeypair]).rpc();
+        }
+        await pause()
+        let rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.pauseFlags, 15)
+
+        // Unpause, and revoke the role
+        await program.methods.unpause().accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+        await program.methods.revokeRole({pauser: {}}, pauserKeypair.publicKey).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            roleGrant,
+        }).rpc();
+
+        try {
+            await pause()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "AccountNotInitialized")
+        }
+        rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.pauseFlags, 0)
+    })
+
+    it("should let a config admin change the agent", async () => {
+        const [roleGrant] = deriveRoleGrantPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            role: 4,
+            member: configAdminKeypair.publicKey,
+        });
+        const newAgentKeypair = Keypair.generate();
+        const changeAgent = async (grant: PublicKey | null) => {
+            await program.methods.changeAgent(newAgentKeypair.publicKey).accounts({
+                manager: configAdminKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                roleGrant: grant,
+            }).signers([configAdminKeypair]).rpc();
+        }
+
+        // Without the role, the member cannot change the agent
+        try {
+            await changeAgent(null)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        await program.methods.grantRole({configAdmin: {}}, configAdminKeypair.publicKey).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            roleGrant,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+        await changeAgent(roleGrant)
+
+        const rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.agent.toBase58(), newAgentKeypair.publicKey.toBase58())
+    })
+
+    it("should only let submitters and config admins allocate claim bitmaps, and keep roles under the timelock", async () => {
+        const grantRole = async (role: any, roleNr: number, member: PublicKey): Promise<PublicKey> => {
+            const [roleGrant] = deriveRoleGrantPDA({
+                rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                role: roleNr,
+                member,
+            });
+            await program.methods.grantRole(role, member).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                roleGrant,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+            return roleGrant
+        }
+
+        // Add epoch 1
+        const mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, Array(32).fill(1), new anchor.BN(10), new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        const initClaimBitmap = async (authority: Keypair, roleGrant: PublicKey) => {
+            await program.methods.initClaimBitmap(epochNr, new anchor.BN(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimBitmap: findClaimBitmapKey({
+                    epochAccount: epoch1,
+                    shard: new anchor.BN(0),
+                    program: program.programId
+                })[0],
+                authority: authority.publicKey,
+                roleGrant,
+                systemProgram: SystemProgram.programId,
+            }).signers([authority]).rpc();
+        }
+
+        // A pauser cannot allocate claim bitmaps, a config admin can
+        const pauserRoleGrant = await grantRole({pauser: {}}, 2, pauserKeypair.publicKey)
+        try {
+            await initClaimBitmap(pauserKeypair, pauserRoleGrant)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+        const configAdminRoleGrant = await grantRole({configAdmin: {}}, 4, configAdminKeypair.publicKey)
+        await initClaimBitmap(configAdminKeypair, configAdminRoleGrant)
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.equal(epochAccount.claimBitmapShards.toNumber(), 1)
+
+        // Enable the timelock
+        const [operation] = deriveTimelockOperationPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            operationNr: new anchor.BN(1),
+        });
+        await program.methods.queueTimelockOperation({setTimelockDelay: {delay: new anchor.BN(2)}}).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            operation,
+            manager: managerKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+        await program.methods.executeTimelockOperation(new anchor.BN(1)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            operation,
+        }).rpc();
+
+        // Only the pauser role is still handed out
+        for (const [role, roleNr] of [[{funder: {}}, 3], [{configAdmin: {}}, 4]] as [any, number][]) {
+            try {
+                await grantRole(role, roleNr, pauserKeypair.publicKey)
+                assert(false, "should've failed but didn't");
+            } catch (_err) {
+                assert.equal(_err.error.errorCode.code, "TimelockRequired")
+            }
+        }
+        await grantRole({pauser: {}}, 2, configAdminKeypair.publicKey)
+    })
+});
@@ -0,0 +1,295 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("rollover epoch tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+
+        // Mint to manager
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+            [], // Multi signers
+            undefined, // Confirm options
+            TOKEN_PROGRAM_ID
+        );
+
+        // Airdrops
+        for (const keypair of [agentKeypair, receiverKeypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+
+        // Manager doesn't need airdrop because the manager is the Anchor wallet
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        rewardsAccountKeypair = Keypair.generate();
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should roll the unclaimed rewards over into the next epoch after the deadline", async () => {
+        const amount = new anchor.BN(10)
+        const nextAmount = new anchor.BN(15)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount: nextAmount,
+            },
+        ])
+        const nextTreeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+
+        const setClaimWindow = async (claimWindow: number) => {
+            await program.methods.setClaimWindow(new anchor.BN(claimWindow)).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+        }
+        const addEpoch = async (epochNr: anchor.BN, root: number[], maxTotalClaim: anchor.BN): Promise<PublicKey> => {
+            const [epoch, epochBump] = deriveEpochPDA({
+                rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                epochNr,
+            });
+            await program.methods.addEpoch(epochBump, root, maxTotalClaim, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                currentEpochAccount: epoch,
+                mint,
+                agent: agentKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([agentKeypair]).rpc();
+            return epoch
+        }
+        const approveEpoch = async (epochNr: anchor.BN, epoch: PublicKey, root: number[], maxTotalClaim: anchor.BN) => {
+            await program.methods.approveEpoch(epochNr, maxTotalClaim, root, new anchor.BN(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch, true),
+                managerTokenAccount,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+
+        // Approve epoch 1 with a one second claim window, and add epoch 2 without approving it
+        await setClaimWindow(1)
+        const epoch1Nr = new anchor.BN(1)
+        const epoch1Root = Array(32).fill(1)
+        const epoch1 = await addEpoch(epoch1Nr, epoch1Root, amount)
+        await approveEpoch(epoch1Nr, epoch1, epoch1Root, amount)
+        const epoch2Nr = new anchor.BN(2)
+        const epoch2 = await addEpoch(epoch2Nr, nextTreeRoot, nextAmount)
+
+        // Let the deadline pass
+        await new Promise((resolve) => setTimeout(resolve, 3000));
+
+        const rolloverEpoch = async () => {
+            await program.methods.rolloverEpoch(epoch1Nr, epoch2Nr).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                nextEpochAccount: epoch2,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+                nextEpochTokenAccount: await getAssociatedTokenAddress(mint, epoch2, true),
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).rpc();
+        }
+        await rolloverEpoch()
+
+        const epoch1Vault = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, epoch1, true))
+        assert.equal(Number(epoch1Vault.amount), 0)
+        const epoch1Account = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(epoch1Account.isSwept)
+        const rolledOverEpochAccount = await program.account.epochAccount.fetch(epoch2)
+        assert.equal(rolledOverEpochAccount.rolledOverAmount.toNumber(), amount.toNumber())
+
+        // The rewards of a mint are rolled over once
+        try {
+            await rolloverEpoch()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "EpochSwept")
+        }
+
+        // Approving epoch 2 only funds what was not rolled over
+        await setClaimWindow(0)
+        const managerBalanceBefore = await getAccount(provider.connection, managerTokenAccount)
+        await approveEpoch(epoch2Nr, epoch2, nextTreeRoot, nextAmount)
+        const managerBalanceAfter = await getAccount(provider.connection, managerTokenAccount)
+        assert.equal(Number(managerBalanceBefore.amount - managerBalanceAfter.amount), nextAmount.sub(amount).toNumber())
+        const epoch2Vault = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, epoch2, true))
+        assert.equal(Number(epoch2Vault.amount), nextAmount.toNumber())
+        const epoch2Account = await program.account.epochAccount.fetch(epoch2)
+        assert.equal(epoch2Account.fundedAmount.toNumber(), nextAmount.toNumber())
+        assert.equal(epoch2Account.issuedAmount.toNumber(), nextAmount.sub(amount).toNumber())
+
+        // The receiver claims the recycled rewards
+        const leafIndex = new anchor.BN(0)
+        const proof = tree.getProof(leafIndex.toNumber(), receiverKeypair.publicKey, nextAmount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+        await program.methods.claim(leafIndex, nextAmount, proof, 0).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch2,
+            claimStatus: findClaimStatusKey({
+                rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                index: leafIndex,
+                epochAccount: epoch2,
+                program: program.programId
+            })[0],
+            from: await getAssociatedTokenAddress(mint, epoch2, true),
+            to: await getAssociatedTokenAddress(mint, receiverKeypair.publicKey),
+            receiver: receiverKeypair.publicKey,
+            mintAccount: mint,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).signers([receiverKeypair]).rpc();
+        const receiverAccount = await getAccount(
+            provider.connection,
+            await getAssociatedTokenAddress(mint, receiverKeypair.publicKey)
+        )
+        assert.equal(Number(receiverAccount.amount), nextAmount.toNumber())
+        assert.equal(Number((await getAccount(provider.connection, await getAssociatedTokenAddress(mint, epoch2, true))).amount), 0)
+    })
+
+    it("should roll over into an approved epoch by crediting its funded amount", async () => {
+        const amount = new anchor.BN(10)
+        const treeRoot = Array(32).fill(1)
+
+        // One second claim window
+        await program.methods.setClaimWindow(new anchor.BN(1)).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        // Add and approve epochs 1 and 2
+        const epochs: PublicKey[] = []
+        for (const epochNr of [new anchor.BN(1), new anchor.BN(2)]) {
+            const [epoch, epochBump] = deriveEpochPDA({
+                rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                epochNr,
+            });
+            await program.methods.addEpoch(epochBump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                currentEpochAccount: epoch,
+                mint,
+                agent: agentKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([agentKeypair]).rpc();
+            await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch, true),
+                managerTokenAccount,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+            epochs.push(epoch)
+        }
+        const [epoch1, epoch2] = epochs
+
+        // Let the deadline pass
+        await new Promise((resolve) => setTimeout(resolve, 3000));
+
+        const rolloverEpoch = async (epochNr: anchor.BN, nextEpochNr: anchor.BN, epoch: PublicKey, nextEpoch: PublicKey) => {
+            await program.methods.rolloverEpoch(epochNr, nextEpochNr).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch,
+                nextEpochAccount: nextEpoch,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount: await getAssociatedTokenAddress(mint, epoch, true),
+                nextEpochTokenAccount: await getAssociatedTokenAddress(mint, nextEpoch, true),
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).rpc();
+        }
+
+        // Rewards only move forward
+        try {
+            await rolloverEpoch(new anchor.BN(2), new anchor.BN(1), epoch2, epoch1)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "InvalidEpochNr")
+        }
+
+        // The vault of epoch 2 is funded already, the rolled over rewards are credited to it
+        await rolloverEpoch(new anchor.BN(1), new anchor.BN(2), epoch1, epoch2)
+        const epoch2Vault = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, epoch2, true))
+        assert.equal(Number(epoch2Vault.amount), amount.muln(2).toNumber())
+        const epoch2Account = await program.account.epochAccount.fetch(epoch2)
+        assert.equal(epoch2Account.fundedAmount.toNumber(), amount.muln(2).toNumber())
+        assert.equal(epoch2Account.rolledOverAmount.toNumber(), 0)
+    })
+});
@@ -0,0 +1,293 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimBitmapKey, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {AnchorError} from "@coral-xyz/anchor";
+import {assert, expect} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("sweep epoch tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+
+        // Mint to manager
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+            [], // Multi signers
+            undefined, // Confirm options
+            TOKEN_PROGRAM_ID
+        );
+
+        // Airdrops
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+
+        // Manager doesn't need airdrop because the manager is the Anchor wallet
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        rewardsAccountKeypair = Keypair.generate();
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    // Tree
+    const amount = new anchor.BN(10)
+    const leafIndex = new anchor.BN(0)
+    const epochNr = new anchor.BN(1)
+
+    /**
+     * Sets a one second claim window, adds and approves epoch 1 paying `amount` to `receiver`,
+     * and lets its deadline pass. Returns the epoch PDA and the proof of the receiver.
+     */
+    const approveExpiredEpoch = async (receiver: PublicKey): Promise<[PublicKey, number[][]]> => {
+        const tree = new BalanceTree([
+            {
+                account: receiver,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+        const proof = tree.getProof(leafIndex.toNumber(), receiver, amount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+
+        // One second claim window
+        await program.methods.setClaimWindow(new anchor.BN(1)).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        // Add and approve epoch 1
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+            managerTokenAccount: await getAssociatedTokenAddress(mint, managerKeypair.publicKey),
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).signers([managerKeypair]).rpc();
+
+        // Let the deadline pass
+        await new Promise((resolve) => setTimeout(resolve, 3000));
+
+        return [epoch1, proof]
+    }
+
+    /**
+     * Sets a new treasury and returns its token account.
+     */
+    const setTreasury = async (): Promise<PublicKey> => {
+        const treasuryKeypair = Keypair.generate();
+        await program.methods.setTreasury(treasuryKeypair.publicKey).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        return await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            treasuryKeypair.publicKey // Owner
+        );
+    }
+
+    /**
+     * Sweeps epoch 1 to `treasuryTokenAccount`.
+     */
+    const sweepEpoch = async (epoch1: PublicKey, treasuryTokenAccount: PublicKey) => {
+        await program.methods.sweepEpoch(epochNr).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount: await getAssociatedTokenAddress(mint, epoch1, true),
+            treasuryTokenAccount,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).rpc();
+    }
+
+    it("should sweep the unclaimed rewards after the deadline", async () => {
+        const receiverKeypair = Keypair.generate();
+        const treasuryTokenAccount = await setTreasury()
+        const [epoch1, proof] = await approveExpiredEpoch(receiverKeypair.publicKey)
+
+        await sweepEpoch(epoch1, treasuryTokenAccount)
+
+        const treasury = await getAccount(provider.connection, treasuryTokenAccount)
+        assert.equal(Number(treasury.amount), amount.toNumber())
+        const epochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isTrue(epochAccount.isSwept)
+
+        // Claims are rejected once swept
+        await confirmedAirdrop(
+            provider.connection,
+            receiverKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+        try {
+            await program.methods.claim(leafIndex, amount, proof, 0).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimStatus: findClaimStatusKey({
+                    rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                    index: leafIndex,
+                    epochAccount: epoch1,
+                    program: program.programId
+                })[0],
+                from: await getAssociatedTokenAddress(mint, epoch1, true),
+                to: await getAssociatedTokenAddress(mint, receiverKeypair.publicKey),
+                receiver: receiverKeypair.publicKey,
+                mintAccount: mint,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).signers([receiverKeypair]).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("EpochSwept");
+        }
+    })
+
+    it("should not sweep while the treasury is unset", async () => {
+        const [epoch1] = await approveExpiredEpoch(Keypair.generate().publicKey)
+
+        // A token account owned by the default pubkey, which the unset treasury matches
+        const defaultOwnedTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            PublicKey.default, // Owner
+            Keypair.generate() // Keypair of the token account
+        );
+
+        try {
+            await sweepEpoch(epoch1, defaultOwnedTokenAccount)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("InvalidTreasury");
+        }
+    })
+
+    it("should not sweep a mint twice", async () => {
+        const treasuryTokenAccount = await setTreasury()
+        const [epoch1] = await approveExpiredEpoch(Keypair.generate().publicKey)
+
+        await sweepEpoch(epoch1, treasuryTokenAccount)
+
+        try {
+            await sweepEpoch(epoch1, treasuryTokenAccount)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("EpochSwept");
+        }
+    })
+
+    it("should not allocate a claim bitmap once swept", async () => {
+        const treasuryTokenAccount = await setTreasury()
+        const [epoch1] = await approveExpiredEpoch(Keypair.generate().publicKey)
+
+        await sweepEpoch(epoch1, treasuryTokenAccount)
+
+        const shard = new anchor.BN(0)
+        try {
+            await program.methods.initClaimBitmap(epochNr, shard).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimBitmap: findClaimBitmapKey({
+                    epochAccount: epoch1,
+                    shard,
+                    program: program.programId
+                })[0],
+                authority: managerKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("EpochSwept");
+        }
+    })
+});
@@ -0,0 +1,160 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveRoleGrantPDA, deriveTimelockOperationPDA} from "../src/utils/pda";
+import {beforeEach} from "mocha";
+import {assert} from "chai";
+
+describe("timelock tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should only execute queued changes after the delay unless cancelled", async () => {
+        const operation = (operationNr: number): PublicKey => deriveTimelockOperationPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            operationNr: new anchor.BN(operationNr),
+        })[0];
+        const queue = async (operationNr: number, action: any) => {
+            await program.methods.queueTimelockOperation(action).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                operation: operation(operationNr),
+                manager: managerKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+        const execute = async (operationNr: number) => {
+            await program.methods.executeTimelockOperation(new anchor.BN(operationNr)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                operation: operation(operationNr),
+            }).rpc();
+        }
+
+        // Without a delay, enabling the timelock executes right away
+        await queue(1, {setTimelockDelay: {delay: new anchor.BN(2)}})
+        await execute(1)
+        let rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.timelockDelay.toNumber(), 2)
+
+        // The agent can no longer be changed directly
+        const newAgentKeypair = Keypair.generate();
+        try {
+            await program.methods.changeAgent(newAgentKeypair.publicKey).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "TimelockRequired")
+        }
+
+        // Nor can the agent quorum, the manager multisig or who approves epochs
+        const agents = [agentKeypair.publicKey, newAgentKeypair.publicKey]
+        try {
+            await program.methods.setAgentQuorum(agents, 2).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "TimelockRequired")
+        }
+        try {
+            await program.methods.setManagerMultisig([managerKeypair.publicKey], 1).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "TimelockRequired")
+        }
+        const approverKeypair = Keypair.generate();
+        try {
+            await program.methods.grantRole({approver: {}}, approverKeypair.publicKey).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                roleGrant: deriveRoleGrantPDA({
+                    rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                    role: 1,
+                    member: approverKeypair.publicKey,
+                })[0],
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "TimelockRequired")
+        }
+
+        // A queued change cannot execute before its eta
+        await queue(2, {changeAgent: {newAgent: newAgentKeypair.publicKey}})
+        try {
+            await execute(2)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "TimelockNotReady")
+        }
+
+        // The manager cancels a queued treasury change
+        await queue(3, {setTreasury: {treasury: Keypair.generate().publicKey}})
+        await program.methods.cancelTimelockOperation(new anchor.BN(3)).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            operation: operation(3),
+        }).rpc();
+
+        // The agent quorum is queued like the other changes, once valid
+        try {
+            await queue(4, {setAgentQuorum: {agents, quorum: 3}})
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "InvalidAgentQuorum")
+        }
+        await queue(4, {setAgentQuorum: {agents, quorum: 2}})
+
+        // Wait for the delay to pass
+        await new Promise((resolve) => setTimeout(resolve, 3000));
+
+        try {
+            await execute(3)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "TimelockOperationNotPending")
+        }
+
+        await execute(2)
+        rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.agent.toBase58(), newAgentKeypair.publicKey.toBase58())
+        const operation2 = await program.account.timelockOperation.fetch(operation(2))
+        assert.isTrue(operation2.isExecuted)
+
+        await execute(4)
+        rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.agentQuorum, 2)
+        assert.equal(rewardsAccount.quorumAgents.length, 2)
+    })
+});
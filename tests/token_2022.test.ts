@@ -0,0 +1,215 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {
+    Keypair,
+    LAMPORTS_PER_SOL,
+    PublicKey,
+    SystemProgram,
+    Transaction,
+    sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {
+    ExtensionType,
+    TOKEN_2022_PROGRAM_ID,
+    createInitializeMintInstruction,
+    createInitializeNonTransferableMintInstruction,
+    createInitializeTransferFeeConfigInstruction,
+    getMintLen,
+} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {AnchorError} from "@coral-xyz/anchor";
+import {assert, expect} from "chai";
+
+const {
+    createAssociatedTokenAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("token 2022 tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    let rewardsAccountKeypair: Keypair
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    /**
+     * Creates a Token-2022 mint with the given extensions, initialized by `initExtensions`.
+     */
+    const createMint2022 = async (extensions: ExtensionType[], initExtensions: (mint: PublicKey) => anchor.web3.TransactionInstruction[]): Promise<PublicKey> => {
+        const mintKeypair = Keypair.generate();
+        const mintLen = getMintLen(extensions);
+        const transaction = new Transaction().add(
+            SystemProgram.createAccount({
+                fromPubkey: meKeypair.publicKey,
+                newAccountPubkey: mintKeypair.publicKey,
+                space: mintLen,
+                lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+                programId: TOKEN_2022_PROGRAM_ID,
+            }),
+            ...initExtensions(mintKeypair.publicKey),
+            createInitializeMintInstruction(mintKeypair.publicKey, 0, meKeypair.publicKey, null, TOKEN_2022_PROGRAM_ID),
+        );
+        await sendAndConfirmTransaction(provider.connection, transaction, [meKeypair, mintKeypair]);
+        return mintKeypair.publicKey
+    }
+
+    before("do airdrops", async () => {
+        await confirmedAirdrop(
+            provider.connection,
+            agentKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+        await confirmedAirdrop(
+            provider.connection,
+            receiverKeypair.publicKey,
+            LAMPORTS_PER_SOL // 1 SOL
+        );
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        rewardsAccountKeypair = Keypair.generate();
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should fund the epoch for the transfer fee and pay the net amount", async () => {
+        // 1% transfer fee
+        const mint = await createMint2022([ExtensionType.TransferFeeConfig], (mint) => [
+            createInitializeTransferFeeConfigInstruction(mint, meKeypair.publicKey, meKeypair.publicKey, 100, BigInt(1_000_000), TOKEN_2022_PROGRAM_ID),
+        ]);
+        const managerTokenAccount = await createAssociatedTokenAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey, // Owner
+            undefined, // Confirm options
+            TOKEN_2022_PROGRAM_ID
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+            [], // Multi signers
+            undefined, // Confirm options
+            TOKEN_2022_PROGRAM_ID
+        );
+
+        // Tree
+        const amount = new anchor.BN(100)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+        const leafIndex = new anchor.BN(0)
+        const proof = tree.getProof(leafIndex.toNumber(), receiverKeypair.publicKey, amount).map((e) =>
+            Array.from(Uint8Array.from(e))
+        )
+
+        // Add and approve epoch 1
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        const epochTokenAccount = await getAssociatedTokenAddress(mint, epoch1, true, TOKEN_2022_PROGRAM_ID)
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount,
+            managerTokenAccount,
+            mintAccount: mint,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).signers([managerKeypair]).rpc();
+
+        // The vault holds the full amount to distribute
+        const vault = await getAccount(provider.connection, epochTokenAccount, undefined, TOKEN_2022_PROGRAM_ID)
+        assert.equal(Number(vault.amount), amount.toNumber())
+
+        // Claim
+        const to = await getAssociatedTokenAddress(mint, receiverKeypair.publicKey, false, TOKEN_2022_PROGRAM_ID)
+        await program.methods.claim(leafIndex, amount, proof, 0).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            claimStatus: findClaimStatusKey({
+                rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                index: leafIndex,
+                epochAccount: epoch1,
+                program: program.programId
+            })[0],
+            from: epochTokenAccount,
+            to,
+            receiver: receiverKeypair.publicKey,
+            mintAccount: mint,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+        }).signers([receiverKeypair]).rpc();
+
+        // The receiver gets the amount minus the 1% fee
+        const receiverTokenAccount = await getAccount(provider.connection, to, undefined, TOKEN_2022_PROGRAM_ID)
+        assert.equal(Number(receiverTokenAccount.amount), 99)
+    })
+
+    it("should reject non-transferable mints", async () => {
+        const mint = await createMint2022([ExtensionType.NonTransferable], (mint) => [
+            createInitializeNonTransferableMintInstruction(mint, TOKEN_2022_PROGRAM_ID),
+        ]);
+
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr: new anchor.BN(1),
+        });
+        try {
+            await program.methods.addEpoch(epoch1Bump, Array(32).fill(0), new anchor.BN(1), new anchor.BN(1), "", Array(32).fill(0)).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                currentEpochAccount: epoch1,
+                mint,
+                agent: agentKeypair.publicKey,
+                systemProgram: SystemProgram.programId,
+            }).signers([agentKeypair]).rpc();
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            expect(_err).to.be.instanceOf(AnchorError);
+            const err: AnchorError = _err;
+            expect(err.error.errorCode.code).to.equal("NonTransferableMint");
+        }
+    })
+});
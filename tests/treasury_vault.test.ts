@@ -0,0 +1,231 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, deriveTreasuryVaultPDA} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("treasury vault tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const depositorKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+    let depositorTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA and mint to it
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Create the depositor ATA and mint to it
+        depositorTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            depositorKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            depositorTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Airdrops
+        for (const keypair of [agentKeypair, depositorKeypair, receiverKeypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should fund epochs from the treasury vault", async () => {
+        const [treasuryVault] = deriveTreasuryVaultPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            mint,
+        })
+
+        // Anyone can deposit in the treasury vault
+        await program.methods.depositTreasury(new anchor.BN(100)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            treasuryVault,
+            depositor: depositorKeypair.publicKey,
+            depositorTokenAccount,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).signers([depositorKeypair]).rpc();
+        assert.equal(Number((await getAccount(provider.connection, treasuryVault)).amount), 100)
+
+        // Tree
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+
+        // Add epoch 1
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+
+        // Approve epoch 1 from the treasury vault, the manager does not pay
+        await program.methods.setChallengeWindow(new anchor.BN(3600)).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+        const epochTokenAccount = await getAssociatedTokenAddress(mint, epoch1, true)
+        await program.methods.approveEpoch(epochNr, amount, treeRoot, new anchor.BN(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+            manager: managerKeypair.publicKey,
+            epochTokenAccount,
+            managerTokenAccount: null,
+            treasuryVault,
+            mintAccount: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        }).rpc();
+        assert.equal(Number((await getAccount(provider.connection, epochTokenAccount)).amount), amount.toNumber())
+        assert.equal(Number((await getAccount(provider.connection, treasuryVault)).amount), 90)
+        assert.equal(Number((await getAccount(provider.connection, managerTokenAccount)).amount), 10_000)
+        const approvedEpochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.deepEqual(approvedEpochAccount.fundingSource, {treasuryVault: {}})
+
+        // A veto refunds the treasury vault, which funded the epoch, not the manager
+        const vetoEpoch = async (vetoManagerTokenAccount: PublicKey | null, vetoTreasuryVault: PublicKey | null) => {
+            await program.methods.vetoEpoch(epochNr).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                authority: managerKeypair.publicKey,
+                epochTokenAccount,
+                managerTokenAccount: vetoManagerTokenAccount,
+                treasuryVault: vetoTreasuryVault,
+                mintAccount: mint,
+                previousEpochAccount: null,
+                previousEpochTokenAccount: null,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).rpc();
+        }
+        try {
+            await vetoEpoch(managerTokenAccount, null)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "MissingFundingSource")
+        }
+
+        await vetoEpoch(null, treasuryVault)
+        assert.equal(Number((await getAccount(provider.connection, epochTokenAccount)).amount), 0)
+        assert.equal(Number((await getAccount(provider.connection, treasuryVault)).amount), 100)
+        assert.equal(Number((await getAccount(provider.connection, managerTokenAccount)).amount), 10_000)
+
+        // Only the manager can withdraw
+        const withdrawTreasury = async (manager: Keypair, to: PublicKey, amount: anchor.BN) => {
+            await program.methods.withdrawTreasury(amount).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                treasuryVault,
+                manager: manager.publicKey,
+                to,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).signers([manager]).rpc();
+        }
+        try {
+            await withdrawTreasury(depositorKeypair, managerTokenAccount, new anchor.BN(100))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        // The tokens only go to the manager or the treasury
+        try {
+            await withdrawTreasury(managerKeypair, depositorTokenAccount, new anchor.BN(100))
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "OwnerMismatch")
+        }
+
+        await withdrawTreasury(managerKeypair, managerTokenAccount, new anchor.BN(100))
+        assert.equal(Number((await getAccount(provider.connection, treasuryVault)).amount), 0)
+        assert.equal(Number((await getAccount(provider.connection, managerTokenAccount)).amount), 10_100)
+    })
+});
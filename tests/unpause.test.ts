@@ -114,6 +114,6 @@ describe("unpause instruction", () => {
     const rewardAccount = await program.account.rewardsAccount.fetch(
       rewardsAccountKeypair.publicKey
     );
-    assert(!rewardAccount.isPaused, "Paused boolean should be false");
+    assert.equal(rewardAccount.pauseFlags, 0, "No pause bit should be set");
   });
 });
@@ -0,0 +1,235 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import {
+    RewardsDistributor,
+} from "../target/types/rewards_distributor";
+import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram} from "@solana/web3.js";
+import {deriveEpochPDA, findClaimStatusKey} from "../src/utils/pda";
+import {BalanceTree} from "../src/libs/balance-tree";
+import {beforeEach} from "mocha";
+import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {confirmedAirdrop} from "./utils";
+import {assert} from "chai";
+
+const {
+    createMint,
+    createAccount,
+    mintTo,
+    getAccount,
+    getAssociatedTokenAddress,
+} = require("@solana/spl-token");
+
+describe("veto epoch tests", () => {
+    // Configure the client to use the local cluster.
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const myWallet = provider.wallet as anchor.Wallet
+    const meKeypair = myWallet.payer
+
+    // Keypairs
+    const rewardsAccountKeypair = Keypair.generate();
+    const managerKeypair = meKeypair
+    const agentKeypair = Keypair.generate();
+    const guardianKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    // Mint
+    let mint: PublicKey
+    let managerTokenAccount: PublicKey
+
+    // Create the mint and airdrop to each member involved
+    before("create mint and do airdrops", async () => {
+        // Mint
+        mint = await createMint(
+            provider.connection,
+            meKeypair, // Payer
+            meKeypair.publicKey, // Mint authority
+            null, // Freeze authority
+            0 // Decimals
+        );
+
+        // Create the manager ATA and mint to it
+        managerTokenAccount = await createAccount(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerKeypair.publicKey // Owner
+        );
+        await mintTo(
+            provider.connection,
+            meKeypair, // Payer
+            mint, // Mint
+            managerTokenAccount, // Destination
+            meKeypair, // Authority
+            10_000, // Amount
+        );
+
+        // Airdrops
+        for (const keypair of [agentKeypair, guardianKeypair, receiverKeypair]) {
+            await confirmedAirdrop(
+                provider.connection,
+                keypair.publicKey,
+                LAMPORTS_PER_SOL // 1 SOL
+            );
+        }
+    })
+
+    // Initialize a new account each time
+    let program: Program<RewardsDistributor>
+    beforeEach("initialize", async () => {
+        program = anchor.workspace.RewardsDistributor as Program<RewardsDistributor>;
+        await program.methods
+            .initialize(agentKeypair.publicKey)
+            .accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            })
+            .signers([rewardsAccountKeypair])
+            .rpc();
+    });
+
+    it("should veto an epoch during its challenge window", async () => {
+        await program.methods.setGuardian(guardianKeypair.publicKey).accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+        const setChallengeWindow = async (challengeWindow: number) => {
+            await program.methods.setChallengeWindow(new anchor.BN(challengeWindow)).accounts({
+                manager: managerKeypair.publicKey,
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+            }).rpc();
+        }
+        await setChallengeWindow(3600)
+
+        // Tree
+        const amount = new anchor.BN(10)
+        const tree = new BalanceTree([
+            {
+                account: receiverKeypair.publicKey,
+                amount,
+            },
+        ])
+        const treeRoot = Array.from(Uint8Array.from(tree.getRoot()))
+
+        // Add and approve epoch 1
+        const epochNr = new anchor.BN(1)
+        const [epoch1, epoch1Bump] = deriveEpochPDA({
+            rewardsAccountKey: rewardsAccountKeypair.publicKey,
+            epochNr,
+        });
+        await program.methods.addEpoch(epoch1Bump, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            currentEpochAccount: epoch1,
+            mint,
+            agent: agentKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+        }).signers([agentKeypair]).rpc();
+        const epochTokenAccount = await getAssociatedTokenAddress(mint, epoch1, true)
+        const approveEpoch = async (correctionCount: anchor.BN) => {
+            await program.methods.approveEpoch(epochNr, amount, treeRoot, correctionCount).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                manager: managerKeypair.publicKey,
+                epochTokenAccount,
+                managerTokenAccount,
+                mintAccount: mint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            }).rpc();
+        }
+        await approveEpoch(new anchor.BN(0))
+
+        // Claims are not open during the challenge window
+        const claim = async () => {
+            const leafIndex = new anchor.BN(0)
+            const proof = tree.getProof(leafIndex.toNumber(), receiverKeypair.publicKey, amount).map((e) =>
+                Array.from(Uint8Array.from(e))
+            )
+            await program.methods.claim(leafIndex, amount, proof, 0).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                claimStatus: findClaimStatusKey({
+                    rewardsAccountKey: rewardsAccountKeypair.publicKey,
+                    index: leafIndex,
+                    epochAccount: epoch1,
+                    program: program.programId
+                })[0],
+                from: epochTokenAccount,
+                to: await getAssociatedTokenAddress(mint, receiverKeypair.publicKey),
+                receiver: receiverKeypair.publicKey,
+                mintAccount: mint,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).signers([receiverKeypair]).rpc();
+        }
+        try {
+            await claim()
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ClaimsNotOpen")
+        }
+
+        // Only the manager or the guardian can veto
+        const vetoEpoch = async (authority: Keypair) => {
+            await program.methods.vetoEpoch(epochNr).accounts({
+                rewardsAccount: rewardsAccountKeypair.publicKey,
+                epochAccount: epoch1,
+                authority: authority.publicKey,
+                epochTokenAccount,
+                managerTokenAccount,
+                mintAccount: mint,
+                previousEpochAccount: null,
+                previousEpochTokenAccount: null,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }).signers([authority]).rpc();
+        }
+        try {
+            await vetoEpoch(agentKeypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "Unauthorized")
+        }
+
+        // The guardian pauses everything, which does not prevent the veto
+        await program.methods.pause().accounts({
+            manager: guardianKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).signers([guardianKeypair]).rpc();
+        const managerBalanceBefore = await getAccount(provider.connection, managerTokenAccount)
+        await vetoEpoch(guardianKeypair)
+
+        // The funded tokens are back and the epoch is unapproved
+        const managerBalanceAfter = await getAccount(provider.connection, managerTokenAccount)
+        assert.equal(Number(managerBalanceAfter.amount - managerBalanceBefore.amount), amount.toNumber())
+        assert.equal(Number((await getAccount(provider.connection, epochTokenAccount)).amount), 0)
+        const vetoedEpochAccount = await program.account.epochAccount.fetch(epoch1)
+        assert.isFalse(vetoedEpochAccount.isApproved)
+        const rewardsAccount = await program.account.rewardsAccount.fetch(rewardsAccountKeypair.publicKey)
+        assert.equal(rewardsAccount.currentApprovedEpoch.toNumber(), 0)
+        await program.methods.unpause().accounts({
+            manager: managerKeypair.publicKey,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+        }).rpc();
+
+        // The agent corrects the epoch and the manager approves it again, without challenge window
+        await program.methods.correctEpoch(epochNr, treeRoot, amount, new anchor.BN(1), "", Array(32).fill(0)).accounts({
+            agent: agentKeypair.publicKey,
+            mint,
+            rewardsAccount: rewardsAccountKeypair.publicKey,
+            epochAccount: epoch1,
+        }).signers([agentKeypair]).rpc();
+        await setChallengeWindow(0)
+        await approveEpoch(new anchor.BN(1))
+
+        // The challenge window is over, so the epoch can no longer be vetoed
+        try {
+            await vetoEpoch(managerKeypair)
+            assert(false, "should've failed but didn't");
+        } catch (_err) {
+            assert.equal(_err.error.errorCode.code, "ChallengeWindowClosed")
+        }
+
+        await claim()
+    })
+});